@@ -1,7 +1,7 @@
 //#![allow(dead_code)]
 use crate::interface;
 use eframe::egui;
-use egui_colors::{utils, Colorix};
+use egui_colors::{tokens::ThemeColor, utils, Colorix};
 use egui_demo_lib::DemoWindows;
 
 #[derive(Default)]
@@ -20,7 +20,11 @@ impl eframe::App for App {
 impl App {
     fn new(ctx: &egui::Context) -> Self {
         ctx.set_theme(egui::Theme::Light);
-        let colorix = Colorix::init(ctx, utils::EGUI_THEME);
+        let mut colorix = Colorix::init(ctx, utils::EGUI_THEME);
+        let registry = colorix.registry_mut();
+        registry.add("Yellow", [ThemeColor::Custom([232, 210, 7]); 12]);
+        registry.add("YellowGreen", [ThemeColor::Custom([178, 194, 31]); 12]);
+        registry.add("Muted Purple", [ThemeColor::Custom([95, 78, 163]); 12]);
         Self {
             colorix,
             ..Default::default()
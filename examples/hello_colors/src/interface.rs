@@ -1,22 +1,24 @@
 use crate::app::App;
 use eframe::egui;
-use egui_colors::tokens::ThemeColor;
 
 pub fn draw_interface(app: &mut App, ctx: &egui::Context) {
-    let names = vec!["Yellow", "YellowGreen", "Muted Purple"];
-    let themes = vec![
-        [ThemeColor::Custom([232, 210, 7]); 12],
-        [ThemeColor::Custom([178, 194, 31]); 12],
-        [ThemeColor::Custom([95, 78, 163]); 12],
-    ];
-    let custom = Some((names, themes));
     egui::TopBottomPanel::top("t_panel").show(ctx, |ui| {
         ui.horizontal_wrapped(|ui| {
-            app.colorix.light_dark_toggle_button(ui);
+            app.colorix.light_dark_system_toggle_button(ui);
             ui.separator();
             ui.toggle_value(&mut app.util_bools[0], "Background Gradient");
             ui.separator();
-            app.colorix.themes_dropdown(ui, custom, false);
+            app.colorix.themes_dropdown(ui);
+            ui.separator();
+            let mut high_contrast = app.colorix.high_contrast();
+            if ui
+                .toggle_value(&mut high_contrast, "High contrast")
+                .changed()
+            {
+                app.colorix.set_high_contrast(ctx, high_contrast);
+            }
+            ui.separator();
+            app.colorix.cvd_preview_dropdown(ui);
         });
     });
     egui::SidePanel::left("left panel").show(ctx, |ui| {
@@ -27,6 +29,10 @@ pub fn draw_interface(app: &mut App, ctx: &egui::Context) {
         app.colorix.custom_picker(ui);
         ui.add_space(20.);
         app.colorix.ui_combo_12(ui);
+        ui.add_space(20.);
+        ui.collapsing("Contrast matrix", |ui| {
+            app.colorix.contrast_matrix(ui);
+        });
     });
     app.demo.ui(ctx);
     egui::CentralPanel::default().show(ctx, |_ui| {
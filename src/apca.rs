@@ -1,3 +1,6 @@
+use egui::Color32;
+use palette::{FromColor, LinSrgb, Okhsl, Srgb};
+
 // originals
 const S_TRC: f32 = 2.4;
 const N_TX: f32 = 0.57;
@@ -16,6 +19,64 @@ const S_RCO: f32 = 0.212_672_9;
 const S_GCO: f32 = 0.715_152_2;
 const S_BCO: f32 = 0.072_175_0;
 
+/// Marks a combination of Lc and font weight that should only be used for non-text elements.
+pub const NON_TEXT: f32 = 777.;
+/// Marks a combination of Lc and font weight that is practically invisible.
+pub const INVISIBLE: f32 = 999.;
+
+/// The minimum font size in px per Lc (first column, in steps of 5) and font weight (100 to 900).
+///
+/// [`NON_TEXT`] and [`INVISIBLE`] mark combinations that are not usable for text.
+#[rustfmt::skip]
+pub const FONT_LOOKUP: [[f32; 10]; 25] = [
+    [0.,   999., 999., 999., 999.,  999.,   999.,   999.,   999., 999.],
+    [10.,  999., 999., 999., 999.,  999.,   999.,   999.,   999., 999.],
+    [15.,  777., 777., 777., 777.,  777.,   777.,   777.,   777., 777.],
+    [20.,  777., 777., 777., 777.,  777.,   777.,   777.,   777., 777.],
+    [25.,  777., 777., 777., 120.,  120.,   108.,   96.,    96.,  96.],
+    [30.,  777., 777., 120., 108.,  108.,   96.,    72.,    72.,  72.],
+    [35.,  777., 120., 108., 96.,   72.,    60.,    48.,    48.,  48.],
+    [40.,  120., 108., 96.,  60.,   48.,    42.,    32.,    32.,  32.],
+    [45.,  108., 96.,  72.,  42.,   32.,    28.,    24.,    24.,  24.],
+    [50.,  96.,  72.,  60.,  32.,   28.,    24.,    21.,    21.,  21.],
+    [55.,  80.,  60.,  48.,  28.,   24.,    21.,    18.,    18.,  18.],
+    [60.,  72.,  48.,  42.,  24.,   21.,    18.,    16.,    16.,  18.],
+    [65.,  68.,  46.,  32.,  21.75, 19.,    17.,    15.,    16.,  18.],
+    [70.,  64.,  44.,  28.,  19.5,  18.,    16.,    14.5,   16.,  18.],
+    [75.,  60.,  42.,  24.,  18.,   16.,    15.,    14.,    16.,  18.],
+    [80.,  56.,  38.25, 23., 17.25, 15.81,  14.81,  14.,    16.,  18.],
+    [85.,  52.,  34.5, 22.,  16.5,  15.625, 14.625, 14.,    16.,  18.],
+    [90.,  48.,  32.,  21.,  16.,   15.5,   14.5,   14.,    16.,  18.],
+    [95.,  45.,  28.,  19.5, 15.5,  15.,    14.,    13.5,   16.,  18.],
+    [100., 42.,  26.5, 18.5, 15.,   14.5,   13.5,   13.,    16.,  18.],
+    [105., 39.,  25.,  18.,  14.,   14.,    13.,    12.,    16.,  18.],
+    [110., 36.,  24.,  18.,  14.,   13.,    12.,    11.,    16.,  18.],
+    [115., 34.5, 22.5, 17.25, 12.5, 11.875, 11.25,  10.625, 14.5, 18.],
+    [120., 33.,  21.,  16.5, 11.,   11.,    10.,    10.,    13.,  18.],
+    [125., 32.,  21.,  16.,  10.,   10.,    10.,    10.,    12.,  18.],
+];
+
+/// Which of text and background is the lighter one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Polarity {
+    /// Dark text on a light background, positive Lc
+    Normal,
+    /// Light text on a dark background, negative Lc
+    Reverse,
+}
+
+impl Polarity {
+    /// The polarity of `text` on `background`.
+    #[must_use]
+    pub fn of(text: Color32, background: Color32) -> Self {
+        if fsc(luminance(background)) > fsc(luminance(text)) {
+            Self::Normal
+        } else {
+            Self::Reverse
+        }
+    }
+}
+
 fn fsc(y: f32) -> f32 {
     if y < B_THRSH {
         y + (B_THRSH - y).powf(B_CLIP)
@@ -47,23 +108,140 @@ fn clamp_contrast(cw: f32) -> f32 {
     }
 }
 
-pub fn estimate_lc(rgb_txt: egui::Color32, rgb_bg: egui::Color32) -> f32 {
-    let r = (f32::from(rgb_txt.r()) / 255.0).powf(S_TRC) * S_RCO;
-    let g = (f32::from(rgb_txt.g()) / 255.0).powf(S_TRC) * S_GCO;
-    let b = (f32::from(rgb_txt.b()) / 255.0).powf(S_TRC) * S_BCO;
-
-    let y = r + g + b;
-
-    let r2 = (f32::from(rgb_bg.r()) / 255.0).powf(S_TRC) * 0.212_672_9;
-    let g2 = (f32::from(rgb_bg.g()) / 255.0).powf(S_TRC) * 0.715_152_2;
-    let b2 = (f32::from(rgb_bg.b()) / 255.0).powf(S_TRC) * 0.072_175_0;
-
-    let y2 = r2 + g2 + b2;
+/// The screen luminance (Y) of a color, before the soft clamp of black levels.
+#[must_use]
+pub fn luminance(color: Color32) -> f32 {
+    let r = (f32::from(color.r()) / 255.0).powf(S_TRC) * S_RCO;
+    let g = (f32::from(color.g()) / 255.0).powf(S_TRC) * S_GCO;
+    let b = (f32::from(color.b()) / 255.0).powf(S_TRC) * S_BCO;
+    r + g + b
+}
 
-    let y_txt = fsc(y);
-    let y_bg = fsc(y2);
+/// The lightness contrast (Lc) of text in `rgb_txt` on `rgb_bg`, following
+/// [APCA](https://github.com/Myndex/apca-w3) 0.0.98G-4g.
+///
+/// Lc goes from about 106 for black on white to about -108 for white on black.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::apca::estimate_lc;
+/// let lc = estimate_lc(egui::Color32::BLACK, egui::Color32::WHITE);
+/// assert!(lc > 100.);
+/// ```
+#[must_use]
+pub fn estimate_lc(rgb_txt: Color32, rgb_bg: Color32) -> f32 {
+    let y_txt = fsc(luminance(rgb_txt));
+    let y_bg = fsc(luminance(rgb_bg));
 
     let cw = determine_cw(y_txt, y_bg);
     let s_apc = clamp_contrast(cw);
     s_apc * 100.0
 }
+
+/// The minimum font size in px for text with this Lc and font weight,
+/// or `None` if the contrast is too low for text of any size.
+///
+/// The sign of `lc` is ignored and `weight` is rounded down to a multiple of 100.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::apca::min_font_size;
+/// assert_eq!(min_font_size(75., 400), Some(18.));
+/// assert_eq!(min_font_size(20., 400), None);
+/// ```
+#[must_use]
+pub fn min_font_size(lc: f32, weight: u16) -> Option<f32> {
+    // a lower Lc and a lighter weight need larger text, so both round down
+    let row = FONT_LOOKUP
+        .iter()
+        .rposition(|row| row[0] <= lc.abs())
+        .unwrap_or(0);
+    let column = usize::from(weight.clamp(100, 900) / 100);
+    let size = FONT_LOOKUP[row][column];
+    (size < NON_TEXT).then_some(size)
+}
+
+fn okhsl_to_color32(okhsl: Okhsl) -> Color32 {
+    let (r, g, b) = Srgb::from_linear(LinSrgb::from_color(okhsl)).into();
+    Color32::from_rgb(r, g, b)
+}
+
+/// The color closest to `text`, only changing its Okhsl lightness, that reaches `target_lc` on `background`.
+///
+/// A positive `target_lc` searches darker text (normal polarity), a negative one lighter text.
+/// `text` is returned unchanged if it already has enough contrast,
+/// and `None` if even black or white does not reach the target.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::apca::{estimate_lc, text_color_for};
+/// use egui::Color32;
+/// let bg = Color32::from_rgb(62, 99, 221);
+/// let text = text_color_for(Color32::from_rgb(110, 150, 240), bg, -75.).unwrap();
+/// assert!(estimate_lc(text, bg) <= -75.);
+/// ```
+#[must_use]
+pub fn text_color_for(text: Color32, background: Color32, target_lc: f32) -> Option<Color32> {
+    let reaches = |color: Color32| reaches_lc(estimate_lc(color, background), target_lc);
+    closest_lightness(text, target_lc >= 0., reaches)
+}
+
+/// The color closest to `background`, only changing its Okhsl lightness, on which `text` reaches `target_lc`.
+///
+/// A positive `target_lc` searches a lighter background (normal polarity), a negative one a darker background.
+/// `background` is returned unchanged if it already has enough contrast,
+/// and `None` if even black or white does not reach the target.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::apca::{background_color_for, estimate_lc};
+/// use egui::Color32;
+/// let bg = background_color_for(Color32::WHITE, Color32::from_rgb(110, 150, 240), -75.).unwrap();
+/// assert!(estimate_lc(Color32::WHITE, bg) <= -75.);
+/// ```
+#[must_use]
+pub fn background_color_for(text: Color32, background: Color32, target_lc: f32) -> Option<Color32> {
+    let reaches = |color: Color32| reaches_lc(estimate_lc(text, color), target_lc);
+    closest_lightness(background, target_lc < 0., reaches)
+}
+
+fn reaches_lc(lc: f32, target_lc: f32) -> bool {
+    if target_lc >= 0. {
+        lc >= target_lc
+    } else {
+        lc <= target_lc
+    }
+}
+
+/// Bisects the Okhsl lightness of `color` towards black or white for the closest color that `reaches`.
+pub(crate) fn closest_lightness(
+    color: Color32,
+    darker: bool,
+    reaches: impl Fn(Color32) -> bool,
+) -> Option<Color32> {
+    if reaches(color) {
+        return Some(color);
+    }
+    let [r, g, b, _] = color.to_array();
+    let mut okhsl = Okhsl::from_color(Srgb::new(r, g, b).into_linear::<f32>());
+    // `near` does not reach the target, `far` does
+    let (mut near, mut far) = (okhsl.lightness, if darker { 0. } else { 1. });
+    okhsl.lightness = far;
+    if !reaches(okhsl_to_color32(okhsl)) {
+        return None;
+    }
+    for _ in 0..24 {
+        okhsl.lightness = (near + far) * 0.5;
+        if reaches(okhsl_to_color32(okhsl)) {
+            far = okhsl.lightness;
+        } else {
+            near = okhsl.lightness;
+        }
+    }
+    okhsl.lightness = far;
+    Some(okhsl_to_color32(okhsl))
+}
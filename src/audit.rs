@@ -0,0 +1,157 @@
+use egui::Color32;
+
+use crate::{apca::estimate_lc, tokens::ColorTokens, utils::LABELS, wcag, ContrastModel};
+
+/// How much contrast a foreground needs on its background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContrastLevel {
+    /// Long texts: Lc 75 or 4.5:1
+    BodyText,
+    /// Labels, buttons and links: Lc 60 or 4.5:1
+    Text,
+    /// Accents, borders and focus rings that have to be seen: Lc 45 or 3:1
+    ///
+    /// Lc 45 is what APCA asks for solid, meaningful non-text like icons. It lies between what 3:1 means
+    /// on light backgrounds (about Lc 55) and on dark ones (about Lc 20).
+    NonText,
+    /// Separators and other decoration, reported without a requirement
+    Decorative,
+}
+
+impl ContrastLevel {
+    /// The minimum absolute Lc.
+    #[must_use]
+    pub const fn min_lc(self) -> f32 {
+        match self {
+            Self::BodyText => 75.,
+            Self::Text => 60.,
+            Self::NonText => 45.,
+            Self::Decorative => 0.,
+        }
+    }
+
+    /// The minimum WCAG 2.x contrast ratio.
+    #[must_use]
+    pub const fn min_ratio(self) -> f32 {
+        match self {
+            Self::BodyText | Self::Text => wcag::AA_TEXT,
+            Self::NonText => wcag::AA_LARGE_TEXT,
+            Self::Decorative => 1.,
+        }
+    }
+
+    /// Whether an Lc or WCAG ratio, depending on `contrast_model`, is enough for this level.
+    #[must_use]
+    pub fn is_met(self, contrast_model: ContrastModel, lc: f32, wcag_ratio: f32) -> bool {
+        match contrast_model {
+            ContrastModel::Apca => lc.abs() >= self.min_lc(),
+            ContrastModel::Wcag => wcag_ratio >= self.min_ratio(),
+        }
+    }
+}
+
+/// The contrast of one foreground/background pair of tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContrastCheck {
+    /// What the pair is used for, e.g. `"body text"`
+    pub use_case: &'static str,
+    /// The name of the foreground token, e.g. `"high contrast text"`
+    pub foreground_token: &'static str,
+    /// The name of the background token
+    pub background_token: &'static str,
+    pub foreground: Color32,
+    pub background: Color32,
+    pub level: ContrastLevel,
+    /// APCA lightness contrast, see [`crate::apca`]
+    pub lc: f32,
+    /// WCAG 2.x contrast ratio, see [`crate::wcag`]
+    pub wcag_ratio: f32,
+    /// Whether the pair has enough contrast for its level, according to the contrast model of the report
+    pub passes: bool,
+}
+
+/// The contrast checks of all meaningful foreground/background pairs of a set of tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastReport {
+    pub contrast_model: ContrastModel,
+    pub checks: Vec<ContrastCheck>,
+}
+
+impl ContrastReport {
+    /// True if every check passes.
+    #[must_use]
+    pub fn passes(&self) -> bool {
+        self.checks.iter().all(|check| check.passes)
+    }
+
+    /// The checks that do not pass.
+    pub fn failures(&self) -> impl Iterator<Item = &ContrastCheck> {
+        self.checks.iter().filter(|check| !check.passes)
+    }
+}
+
+const ON_ACCENT: &str = "on accent";
+
+/// (use case, foreground token, background token, level), tokens are indices in `LABELS`
+/// or `None` for the color on accents.
+const PAIRS: [(&str, Option<usize>, usize, ContrastLevel); 16] = [
+    ("body text", Some(11), 0, ContrastLevel::BodyText),
+    ("body text in panels", Some(11), 1, ContrastLevel::BodyText),
+    ("secondary text", Some(10), 0, ContrastLevel::Text),
+    ("secondary text in panels", Some(10), 1, ContrastLevel::Text),
+    ("button text", Some(10), 2, ContrastLevel::Text),
+    ("hovered button text", Some(11), 3, ContrastLevel::Text),
+    ("pressed button text", Some(11), 4, ContrastLevel::Text),
+    ("hyperlinks", Some(9), 1, ContrastLevel::Text),
+    ("text on accents", None, 8, ContrastLevel::Text),
+    ("text on hovered accents", None, 9, ContrastLevel::Text),
+    ("accents", Some(8), 0, ContrastLevel::NonText),
+    ("focus rings", Some(6), 0, ContrastLevel::NonText),
+    ("widget borders", Some(6), 2, ContrastLevel::NonText),
+    ("hovered widget borders", Some(7), 3, ContrastLevel::NonText),
+    ("separators", Some(5), 0, ContrastLevel::Decorative),
+    ("panel separators", Some(5), 1, ContrastLevel::Decorative),
+];
+
+/// Checks the contrast of every meaningful foreground/background pair of `tokens`.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{audit, Colorix, ContrastModel};
+/// let colorix = Colorix::init(&egui::Context::default(), egui_colors::utils::EGUI_THEME);
+/// let report = audit::contrast_report(&colorix.tokens, ContrastModel::Apca);
+/// for check in report.failures() {
+///     println!("{}: Lc {:.0}", check.use_case, check.lc);
+/// }
+/// ```
+#[must_use]
+pub fn contrast_report(tokens: &ColorTokens, contrast_model: ContrastModel) -> ContrastReport {
+    let checks = PAIRS
+        .iter()
+        .map(|&(use_case, fg, bg, level)| {
+            let (foreground_token, foreground) = fg.map_or((ON_ACCENT, tokens.on_accent), |i| {
+                (LABELS[i], tokens.get_token(i))
+            });
+            let background = tokens.get_token(bg);
+            let lc = estimate_lc(foreground, background);
+            let wcag_ratio = wcag::contrast_ratio(foreground, background);
+            let passes = level.is_met(contrast_model, lc, wcag_ratio);
+            ContrastCheck {
+                use_case,
+                foreground_token,
+                background_token: LABELS[bg],
+                foreground,
+                background,
+                level,
+                lc,
+                wcag_ratio,
+                passes,
+            }
+        })
+        .collect();
+    ContrastReport {
+        contrast_model,
+        checks,
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{parse::ParseThemeError, tokens::ThemeColor, utils::LABELS, Theme};
+
+/// Builds a theme from a base theme and overrides of single tokens, so variants can share a base.
+///
+/// Tokens are named as in [`crate::tokens::ColorTokens`], with spaces, dashes or underscores:
+/// `solid backgrounds`, `solid-backgrounds` and `solid_backgrounds` are the same token.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{builder::ThemeBuilder, tokens::ThemeColor, utils};
+/// let theme = ThemeBuilder::new(utils::INDIGO_JADE)
+///     .set("solid_backgrounds", ThemeColor::Crimson)
+///     .set("hovered solid backgrounds", ThemeColor::Crimson)
+///     .build()?;
+/// assert_eq!(theme[8], ThemeColor::Crimson);
+/// assert_eq!(theme[0], utils::INDIGO_JADE[0]);
+/// # Ok::<(), egui_colors::parse::ParseThemeError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeBuilder {
+    theme: Theme,
+    error: Option<ParseThemeError>,
+}
+
+impl ThemeBuilder {
+    #[must_use]
+    pub const fn new(base: Theme) -> Self {
+        Self {
+            theme: base,
+            error: None,
+        }
+    }
+
+    /// Override the color of a token. An unknown name makes [`ThemeBuilder::build`] fail.
+    #[must_use]
+    pub fn set(mut self, token: &str, color: ThemeColor) -> Self {
+        match token_index(token) {
+            Some(i) => self.theme[i] = color,
+            None => {
+                self.error
+                    .get_or_insert_with(|| ParseThemeError::UnknownToken(token.to_owned()));
+            }
+        }
+        self
+    }
+
+    /// Override the color of a token with a color as text, see [`ThemeColor`]'s `FromStr`.
+    #[must_use]
+    pub fn set_str(self, token: &str, color: &str) -> Self {
+        match color.parse() {
+            Ok(color) => self.set(token, color),
+            Err(err) => Self {
+                error: self.error.or(Some(err)),
+                ..self
+            },
+        }
+    }
+
+    /// Returns the theme with all overrides applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ParseThemeError`] of an unknown token name or invalid color.
+    pub fn build(self) -> Result<Theme, ParseThemeError> {
+        self.error.map_or(Ok(self.theme), Err)
+    }
+}
+
+/// The index of a token in a [`Theme`], by name.
+fn token_index(name: &str) -> Option<usize> {
+    let name = name.trim().to_lowercase().replace(['-', '_'], " ");
+    LABELS.iter().position(|label| *label == name)
+}
@@ -0,0 +1,86 @@
+use egui::Color32;
+use palette::{LinSrgb, Srgb};
+
+/// A type of color vision deficiency, simulated at full severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorBlindness {
+    /// No red cones
+    Protanopia,
+    /// No green cones
+    Deuteranopia,
+    /// No blue cones
+    Tritanopia,
+    /// No color vision at all
+    Achromatopsia,
+}
+
+// Machado, Oliveira and Fernandes (2009), severity 1.0, for linear sRGB
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152_286, 1.052_583, -0.204_868],
+    [0.114_503, 0.786_281, 0.099_216],
+    [-0.003_882, -0.048_116, 1.051_998],
+];
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367_322, 0.860_646, -0.227_968],
+    [0.280_085, 0.672_501, 0.047_413],
+    [-0.011_820, 0.042_940, 0.968_881],
+];
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255_528, -0.076_749, -0.178_779],
+    [-0.078_411, 0.930_809, 0.147_602],
+    [0.004_733, 0.691_367, 0.303_900],
+];
+// every channel becomes the relative luminance
+const ACHROMATOPSIA: [[f32; 3]; 3] = [[0.2126, 0.7152, 0.0722]; 3];
+
+impl ColorBlindness {
+    pub const ALL: [Self; 4] = [
+        Self::Protanopia,
+        Self::Deuteranopia,
+        Self::Tritanopia,
+        Self::Achromatopsia,
+    ];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Protanopia => "Protanopia",
+            Self::Deuteranopia => "Deuteranopia",
+            Self::Tritanopia => "Tritanopia",
+            Self::Achromatopsia => "Achromatopsia",
+        }
+    }
+
+    const fn matrix(self) -> &'static [[f32; 3]; 3] {
+        match self {
+            Self::Protanopia => &PROTANOPIA,
+            Self::Deuteranopia => &DEUTERANOPIA,
+            Self::Tritanopia => &TRITANOPIA,
+            Self::Achromatopsia => &ACHROMATOPSIA,
+        }
+    }
+
+    /// How `color` looks with this color vision deficiency. The alpha is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::cvd::ColorBlindness;
+    /// let red = egui::Color32::from_rgb(229, 72, 77);
+    /// let gray = ColorBlindness::Achromatopsia.simulate(red);
+    /// assert!(gray.r() == gray.g() && gray.g() == gray.b());
+    /// ```
+    #[must_use]
+    pub fn simulate(self, color: Color32) -> Color32 {
+        let [r, g, b, a] = color.to_srgba_unmultiplied();
+        let rgb: LinSrgb = Srgb::new(r, g, b).into_linear();
+        let [r, g, b] = self.matrix().map(|row| {
+            row[2]
+                .mul_add(rgb.blue, row[0].mul_add(rgb.red, row[1] * rgb.green))
+                .clamp(0.0, 1.0)
+        });
+        let (r, g, b) = Srgb::from_linear(LinSrgb::new(r, g, b)).into();
+        Color32::from_rgba_unmultiplied(r, g, b, a)
+    }
+}
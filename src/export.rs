@@ -0,0 +1,307 @@
+use std::fmt::Write;
+
+use egui::Color32;
+
+use crate::{
+    scales::Scale,
+    tokens::{ColorTokens, ThemeColor},
+    utils::LABELS,
+    Colorix, ContrastModel, Theme,
+};
+
+/// What the exporters need: the theme, its light and dark tokens and how to compute its scales.
+struct Source {
+    theme: Theme,
+    contrast_model: ContrastModel,
+    light: ColorTokens,
+    dark: ColorTokens,
+}
+
+impl Source {
+    fn from_theme(theme: &Theme) -> Self {
+        let contrast_model = ContrastModel::default();
+        Self {
+            theme: *theme,
+            contrast_model,
+            light: ColorTokens::from_theme(theme, false, contrast_model),
+            dark: ColorTokens::from_theme(theme, true, contrast_model),
+        }
+    }
+
+    const fn from_colorix(colorix: &Colorix) -> Self {
+        Self {
+            theme: colorix.theme,
+            contrast_model: colorix.contrast_model(),
+            light: *colorix.tokens_of(egui::Theme::Light),
+            dark: *colorix.tokens_of(egui::Theme::Dark),
+        }
+    }
+
+    const fn tokens(&self, dark_mode: bool) -> &ColorTokens {
+        if dark_mode {
+            &self.dark
+        } else {
+            &self.light
+        }
+    }
+
+    fn scale(&self, color: ThemeColor, dark_mode: bool) -> [Color32; 12] {
+        Scale::with_model(color, dark_mode, self.contrast_model).colors
+    }
+}
+
+pub(crate) fn hex(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// The name of a token as used in the exported formats, e.g. `app-background`.
+pub(crate) fn token_name(i: usize) -> String {
+    LABELS[i].replace(' ', "-")
+}
+
+/// The name of a color scale, e.g. `gray` or `custom-172d4d`.
+fn scale_name(color: ThemeColor) -> String {
+    match color {
+        ThemeColor::Custom([r, g, b]) => format!("custom-{r:02x}{g:02x}{b:02x}"),
+        _ => color.label().to_lowercase(),
+    }
+}
+
+/// The colors of a theme without duplicates, in order of appearance.
+fn distinct_colors(theme: &Theme) -> Vec<ThemeColor> {
+    let mut colors = Vec::with_capacity(theme.len());
+    for color in theme {
+        if !colors.contains(color) {
+            colors.push(*color);
+        }
+    }
+    colors
+}
+
+fn css_properties(tokens: &ColorTokens, indent: &str) -> String {
+    let mut out = String::new();
+    for i in 0..LABELS.len() {
+        let _ = writeln!(
+            out,
+            "{indent}--{}: {};",
+            token_name(i),
+            hex(tokens.get_token(i))
+        );
+    }
+    let _ = writeln!(out, "{indent}--on-accent: {};", hex(tokens.on_accent));
+    out
+}
+
+/// Exports the tokens of a theme as CSS custom properties,
+/// with a light and a dark variant selected by `prefers-color-scheme`.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{export, utils};
+/// let css = export::css(&utils::INDIGO_JADE);
+/// assert!(css.contains("--app-background: #"));
+/// assert!(css.contains("@media (prefers-color-scheme: dark)"));
+/// ```
+#[must_use]
+pub fn css(theme: &Theme) -> String {
+    css_of_source(&Source::from_theme(theme))
+}
+
+/// Like [`css`], with the tokens that a [`Colorix`] shows, i.e. with its contrast model,
+/// semantic colors and high contrast setting.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{export, Colorix, ContrastModel};
+/// let ctx = egui::Context::default();
+/// let mut colorix = Colorix::init(&ctx, egui_colors::utils::INDIGO_JADE);
+/// colorix.set_contrast_model(&ctx, ContrastModel::Wcag);
+/// colorix.set_high_contrast(&ctx, true);
+/// let on_accent = colorix.tokens_of(egui::Theme::Dark).on_accent;
+/// let hex = format!("#{:02x}{:02x}{:02x}", on_accent.r(), on_accent.g(), on_accent.b());
+/// assert!(export::css_of(&colorix).contains(&format!("--on-accent: {hex};")));
+/// let text = colorix.tokens_of(egui::Theme::Light).high_contrast_text;
+/// let hex = format!("#{:02x}{:02x}{:02x}", text.r(), text.g(), text.b());
+/// assert!(export::css_of(&colorix).contains(&format!("--high-contrast-text: {hex};")));
+/// ```
+#[must_use]
+pub fn css_of(colorix: &Colorix) -> String {
+    css_of_source(&Source::from_colorix(colorix))
+}
+
+fn css_of_source(source: &Source) -> String {
+    [("light", false), ("dark", true)]
+        .map(|(scheme, dark_mode)| {
+            format!(
+                "@media (prefers-color-scheme: {scheme}) {{\n  :root {{\n{}  }}\n}}\n",
+                css_properties(source.tokens(dark_mode), "    ")
+            )
+        })
+        .join("\n")
+}
+
+#[cfg(feature = "json")]
+fn dtcg_color(color: Color32) -> serde_json::Value {
+    serde_json::json!({ "$type": "color", "$value": hex(color) })
+}
+
+/// Exports a theme in the [Design Tokens](https://tr.designtokens.org/format/) (DTCG) JSON format.
+///
+/// The `theme` group holds the 12 colors of the theme itself, `light` and `dark` hold the computed tokens
+/// and the 12 steps of every color scale in the theme.
+/// Use [`crate::parse::theme_from_dtcg`] to read it back.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{export, parse, utils};
+/// let json = export::dtcg(&utils::INDIGO_JADE);
+/// assert!(json.contains("\"$type\": \"color\""));
+/// assert!(parse::theme_from_dtcg(&json).is_ok());
+/// ```
+#[cfg(feature = "json")]
+#[must_use]
+pub fn dtcg(theme: &Theme) -> String {
+    dtcg_of_source(&Source::from_theme(theme))
+}
+
+/// Like [`dtcg`], with the tokens and scales that a [`Colorix`] shows.
+#[cfg(feature = "json")]
+#[must_use]
+pub fn dtcg_of(colorix: &Colorix) -> String {
+    dtcg_of_source(&Source::from_colorix(colorix))
+}
+
+#[cfg(feature = "json")]
+fn dtcg_of_source(source: &Source) -> String {
+    let theme = &source.theme;
+    let mut root = serde_json::Map::new();
+    root.insert(
+        "theme".to_owned(),
+        theme
+            .iter()
+            .enumerate()
+            .map(|(i, color)| {
+                let [r, g, b] = color.rgb();
+                (token_name(i), dtcg_color(Color32::from_rgb(r, g, b)))
+            })
+            .collect(),
+    );
+    for (mode, dark_mode) in [("light", false), ("dark", true)] {
+        let tokens = source.tokens(dark_mode);
+        let mut group = serde_json::Map::new();
+        let mut token_group: serde_json::Map<String, serde_json::Value> = (0..LABELS.len())
+            .map(|i| (token_name(i), dtcg_color(tokens.get_token(i))))
+            .collect();
+        token_group.insert("on-accent".to_owned(), dtcg_color(tokens.on_accent));
+        group.insert("tokens".to_owned(), token_group.into());
+        for color in distinct_colors(theme) {
+            let scale = source.scale(color, dark_mode);
+            group.insert(
+                scale_name(color),
+                scale
+                    .iter()
+                    .enumerate()
+                    .map(|(i, step)| ((i + 1).to_string(), dtcg_color(*step)))
+                    .collect(),
+            );
+        }
+        root.insert(mode.to_owned(), group.into());
+    }
+    serde_json::to_string_pretty(&root).unwrap_or_default()
+}
+
+/// Exports the scales of a theme as a GIMP/Inkscape `.gpl` palette.
+///
+/// Every distinct color of the theme adds 12 swatches for light mode and 12 for dark mode,
+/// named like `indigo light 9`.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{export, utils};
+/// let gpl = export::gpl(&utils::INDIGO_JADE, "Indigo/jade");
+/// assert!(gpl.starts_with("GIMP Palette"));
+/// ```
+#[must_use]
+pub fn gpl(theme: &Theme, name: &str) -> String {
+    gpl_of_source(&Source::from_theme(theme), name)
+}
+
+/// Like [`gpl`], with the scales that a [`Colorix`] shows.
+#[must_use]
+pub fn gpl_of(colorix: &Colorix, name: &str) -> String {
+    gpl_of_source(&Source::from_colorix(colorix), name)
+}
+
+fn gpl_of_source(source: &Source, name: &str) -> String {
+    let mut out = format!("GIMP Palette\nName: {name}\nColumns: 12\n#\n");
+    for color in distinct_colors(&source.theme) {
+        for (mode, dark_mode) in [("light", false), ("dark", true)] {
+            for (i, step) in source.scale(color, dark_mode).iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "{:>3} {:>3} {:>3}\t{} {mode} {}",
+                    step.r(),
+                    step.g(),
+                    step.b(),
+                    scale_name(color),
+                    i + 1
+                );
+            }
+        }
+    }
+    out
+}
+
+/// Exports the scales of a theme as the colors of a Tailwind config.
+///
+/// Every distinct color of the theme becomes a scale with the steps `1` to `12`,
+/// e.g. `indigo` for light mode and `indigo-dark` for dark mode.
+/// The output is a JSON object of the form `{ "theme": { "extend": { "colors": ... } } }`,
+/// to merge into the object that `tailwind.config.js` exports with `module.exports = {...}`.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{export, utils};
+/// let config = export::tailwind(&utils::INDIGO_JADE);
+/// assert!(config.contains("\"indigo-dark\": {"));
+/// ```
+#[must_use]
+pub fn tailwind(theme: &Theme) -> String {
+    tailwind_of_source(&Source::from_theme(theme))
+}
+
+/// Like [`tailwind`], with the scales that a [`Colorix`] shows.
+#[must_use]
+pub fn tailwind_of(colorix: &Colorix) -> String {
+    tailwind_of_source(&Source::from_colorix(colorix))
+}
+
+fn tailwind_of_source(source: &Source) -> String {
+    let scales: Vec<String> = distinct_colors(&source.theme)
+        .into_iter()
+        .flat_map(|color| [(color, false), (color, true)])
+        .map(|(color, dark_mode)| {
+            let steps: Vec<String> = source
+                .scale(color, dark_mode)
+                .iter()
+                .enumerate()
+                .map(|(i, step)| format!("          \"{}\": \"{}\"", i + 1, hex(*step)))
+                .collect();
+            let suffix = if dark_mode { "-dark" } else { "" };
+            format!(
+                "        \"{}{suffix}\": {{\n{}\n        }}",
+                scale_name(color),
+                steps.join(",\n")
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"theme\": {{\n    \"extend\": {{\n      \"colors\": {{\n{}\n      }}\n    }}\n  }}\n}}\n",
+        scales.join(",\n")
+    )
+}
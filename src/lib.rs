@@ -9,19 +9,122 @@
 //!
 //!
 
-pub(crate) mod apca;
-pub(crate) mod scales;
+/// The APCA contrast algorithm and readable text colors
+pub mod apca;
+/// Check the contrast of the tokens
+pub mod audit;
+/// Build themes from a base theme and overrides
+pub mod builder;
+/// Simulate color vision deficiencies
+pub mod cvd;
+/// Export themes to other formats
+pub mod export;
+/// Load themes from a directory, with hot reload
+#[cfg(feature = "loader")]
+pub mod loader;
+/// Map tokens to egui visuals
+pub mod mapping;
+/// Parse themes from text
+pub mod parse;
+/// Named themes with stable ids
+pub mod registry;
+/// Compute the 12 step color scales
+pub mod scales;
+/// Rounding, stroke widths and shadows
+pub mod shape;
 pub mod tokens;
 /// Some predefined themes
 pub mod utils;
+/// The WCAG 2.x contrast ratio
+pub mod wcag;
 
-use scales::Scales;
-use tokens::{ColorTokens, ThemeColor};
-use utils::{LABELS, THEMES, THEME_NAMES};
+use cvd::ColorBlindness;
+use mapping::{DefaultMapping, VisualsMapping};
+use registry::{ThemeId, ThemeRegistry};
+use scales::{Scale, Scales};
+use shape::StyleShape;
+use std::sync::Arc;
+use tokens::{ColorTokens, SemanticColors, SemanticTokens, ThemeColor};
+use utils::{LABELS, PRESETS};
 
 /// A set of colors that are used together to set a visual feel for the ui
+///
+/// With the `serde` feature enabled a theme can be (de)serialized,
+/// as both [`ThemeColor`] and fixed size arrays implement `Serialize` and `Deserialize`.
 pub type Theme = [ThemeColor; 12];
 
+/// The contrast algorithm used to decide the color on accents and to adjust the scales.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContrastModel {
+    /// APCA lightness contrast (Lc), see [`apca`]
+    #[default]
+    Apca,
+    /// WCAG 2.x contrast ratio, see [`wcag`]
+    Wcag,
+}
+
+impl ContrastModel {
+    /// The contrast of white text on `background`: the absolute Lc or the WCAG ratio.
+    #[must_use]
+    pub fn contrast_of_white(self, background: egui::Color32) -> f32 {
+        match self {
+            Self::Apca => apca::estimate_lc(egui::Color32::WHITE, background).abs(),
+            Self::Wcag => wcag::contrast_ratio(egui::Color32::WHITE, background),
+        }
+    }
+
+    /// The contrast white text needs on an accent (`solid_backgrounds`).
+    pub(crate) const fn on_accent_threshold(self) -> f32 {
+        match self {
+            Self::Apca => 46.,
+            Self::Wcag => wcag::AA_TEXT,
+        }
+    }
+
+    /// Below this contrast with white, step 9 of a light scale is made lighter.
+    pub(crate) const fn light_scale_threshold(self) -> f32 {
+        match self {
+            Self::Apca => 46.,
+            Self::Wcag => wcag::AA_LARGE_TEXT,
+        }
+    }
+
+    /// Above this contrast with white, step 9 of a dark scale is too dark and made lighter.
+    pub(crate) const fn dark_scale_threshold(self) -> f32 {
+        match self {
+            Self::Apca => 95.4,
+            // about the same luminance as Lc 95.4
+            Self::Wcag => 9.15,
+        }
+    }
+}
+
+/// The part of a [`Colorix`] that is worth keeping between sessions.
+///
+/// Store it with [`Colorix::save`] in the egui memory, or in any other storage
+/// (e.g. `eframe::set_value` in `eframe::App::save`) and restore it with [`Colorix::init_with_state`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ColorixState {
+    pub theme: Theme,
+    /// The selected theme of the registry, `None` if the theme was edited
+    pub theme_id: Option<ThemeId>,
+    pub theme_preference: egui::ThemePreference,
+    /// Hue, saturation, value and alpha of the custom color picker
+    pub custom: [f32; 4],
+}
+
+/// A running transition from the tokens that were shown when the colors changed
+#[derive(Debug, Clone, Copy)]
+struct Transition {
+    from: ColorTokens,
+    start: f64,
+}
+
+#[cfg(feature = "serde")]
+const STATE_ID: &str = "egui_colors_state";
+
 /// The Colorix type is the main entry to this crate.
 ///
 /// # Examples
@@ -51,11 +154,28 @@ pub type Theme = [ThemeColor; 12];
 /// ```
 #[derive(Debug, Default, Clone)]
 pub struct Colorix {
+    /// The tokens of the active light or dark mode
     pub tokens: ColorTokens,
-    //pub tokens2: ColorTokens,
+    light_tokens: ColorTokens,
+    dark_tokens: ColorTokens,
     pub(crate) theme: Theme,
-    theme_index: usize,
+    semantic_colors: SemanticColors,
+    shape: StyleShape,
+    visuals_mapping: Option<Arc<dyn VisualsMapping>>,
+    /// Only style the `Ui`s passed to [`Colorix::scope`], not the whole `Context`
+    scoped: bool,
+    /// In seconds, 0 means no transition
+    transition_duration: f32,
+    transition: Option<Transition>,
+    /// Whether `sync_theme` was ever called, without it there is nothing to drive a transition
+    synced: bool,
+    cvd_preview: Option<ColorBlindness>,
+    high_contrast: bool,
+    registry: ThemeRegistry,
+    theme_id: Option<ThemeId>,
     pub(crate) scales: Scales,
+    pasted_theme: String,
+    paste_error: Option<parse::ParseThemeError>,
 }
 
 impl Colorix {
@@ -65,136 +185,488 @@ impl Colorix {
             theme,
             ..Default::default()
         };
-        colorix.scales.dark_mode = ctx.style().visuals.dark_mode;
-        colorix.get_theme_index();
+        colorix.scales.dark_mode = ctx.theme() == egui::Theme::Dark;
+        colorix.find_theme_id();
         colorix.update_colors(ctx);
         colorix
     }
 
-    fn get_theme_index(&mut self) {
-        if let Some(i) = THEMES.iter().position(|t| t == &self.theme) {
-            self.theme_index = i;
+    /// Initialize a Colorix that doesn't style the whole `Context`,
+    /// but only the `Ui`s it is applied to with [`Colorix::scope`] or [`Colorix::style_ui`].
+    /// This way a panel, window or card can use a different theme than the rest of the app.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{utils, Colorix};
+    /// let ctx = egui::Context::default();
+    /// let mut colorix = Colorix::init(&ctx, utils::EGUI_THEME);
+    /// let style = ctx.style();
+    /// let mut card_colorix = Colorix::init_scoped(&ctx, utils::WARM);
+    /// let (mut outside, mut inside) = (None, None);
+    /// let _ = ctx.run(Default::default(), |ctx| {
+    ///     egui::CentralPanel::default().show(ctx, |ui| {
+    ///         ui.label("Egui theme");
+    ///         outside = Some(ui.visuals().clone());
+    ///         card_colorix.scope(ui, |ui| {
+    ///             inside = Some(ui.visuals().clone());
+    ///             egui::Frame::group(ui.style()).show(ui, |ui| ui.label("Warm theme"));
+    ///         });
+    ///     });
+    /// });
+    /// let (outside, inside) = (outside.unwrap(), inside.unwrap());
+    /// assert_eq!(inside.panel_fill, card_colorix.tokens.subtle_background);
+    /// assert_eq!(inside.selection.bg_fill, card_colorix.tokens.solid_backgrounds);
+    /// assert_eq!(outside.selection.bg_fill, colorix.tokens.solid_backgrounds);
+    /// assert_ne!(inside.selection.bg_fill, outside.selection.bg_fill);
+    /// // the scoped Colorix leaves the style of the context alone
+    /// assert_eq!(ctx.style(), style);
+    /// ```
+    #[allow(clippy::must_use_candidate)]
+    pub fn init_scoped(ctx: &egui::Context, theme: Theme) -> Self {
+        let mut colorix = Self {
+            theme,
+            scoped: true,
+            ..Default::default()
         };
+        colorix.find_theme_id();
+        colorix.update_colors(ctx);
+        colorix
     }
-    /// WARNING: don't use the `light_dark` buttons that Egui provides.
-    /// That will override the theme from this crate.
-    pub fn light_dark_toggle_button(&mut self, ui: &mut egui::Ui) {
-        #![allow(clippy::collapsible_else_if)]
-        if ui.ctx().style().visuals.dark_mode {
-            self.scales.dark_mode = true;
-            if ui
-                .add(
-                    egui::Button::new(egui::RichText::new("☀").size(20.))
-                        .min_size(egui::Vec2::new(30., 30.))
-                        .frame(false),
-                )
-                .on_hover_text("Switch to light mode")
-                .clicked()
-            {
-                self.scales.dark_mode = false;
-                ui.ctx().set_visuals(egui::Visuals {
-                    dark_mode: false,
-                    ..Default::default()
-                });
-                self.update_colors(ui.ctx());
+
+    /// Show `add_contents` in a child `Ui` that is styled with this theme.
+    pub fn scope<R>(
+        &mut self,
+        ui: &mut egui::Ui,
+        add_contents: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> egui::InnerResponse<R> {
+        ui.scope(|ui| {
+            self.style_ui(ui);
+            add_contents(ui)
+        })
+    }
+
+    /// Style a single `Ui` (and its children) with this theme.
+    pub fn style_ui(&mut self, ui: &mut egui::Ui) {
+        self.sync_theme(ui.ctx());
+        *ui.visuals_mut() = self.visuals_of_tokens(&self.tokens, self.scales.dark_mode);
+    }
+
+    /// The egui visuals of this theme in light or dark mode.
+    /// Useful to style a frame outside of a `Ui`, e.g. `egui::Frame::window`.
+    ///
+    /// NOTE: this includes the simulation of [`Colorix::set_cvd_preview`].
+    #[must_use]
+    pub fn visuals(&self, theme: egui::Theme) -> egui::Visuals {
+        self.visuals_of_tokens(self.tokens_of(theme), theme == egui::Theme::Dark)
+    }
+
+    fn visuals_of_tokens(&self, tokens: &ColorTokens, dark_mode: bool) -> egui::Visuals {
+        let tokens = self
+            .cvd_preview
+            .map_or(*tokens, |color_blindness| tokens.simulate(color_blindness));
+        tokens.visuals(&self.shape, self.mapping(), dark_mode)
+    }
+
+    fn mapping(&self) -> &dyn VisualsMapping {
+        self.visuals_mapping.as_deref().unwrap_or(&DefaultMapping)
+    }
+
+    /// Initialize a Colorix from a previously saved state.
+    /// This also sets the theme preference of the `Context`.
+    /// The saved theme selection is only kept if it still points to the same colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{tokens::{ColorTokens, ThemeColor}, utils, Colorix, ColorixState};
+    /// let ctx = egui::Context::default();
+    /// let mut state = Colorix::init(&ctx, utils::WARM).state(&ctx);
+    /// state.theme = utils::COOL;
+    /// let colorix = Colorix::init_with_state(&ctx, state);
+    /// assert_eq!(colorix.theme_id(), colorix.registry().id_of(&utils::COOL));
+    ///
+    /// // the state, and the tokens, survive a round trip through a serde format
+    /// state.theme[8] = ThemeColor::Custom([232, 210, 7]);
+    /// let json = serde_json::to_string(&state).unwrap();
+    /// assert_eq!(serde_json::from_str::<ColorixState>(&json).unwrap(), state);
+    /// let json = serde_json::to_string(&colorix.tokens).unwrap();
+    /// let tokens: ColorTokens = serde_json::from_str(&json).unwrap();
+    /// assert_eq!(format!("{tokens:?}"), format!("{:?}", colorix.tokens));
+    /// ```
+    #[cfg(feature = "serde")]
+    #[allow(clippy::must_use_candidate)]
+    pub fn init_with_state(ctx: &egui::Context, state: ColorixState) -> Self {
+        ctx.set_theme(state.theme_preference);
+        let mut colorix = Self {
+            theme: state.theme,
+            theme_id: state.theme_id,
+            ..Default::default()
+        };
+        colorix.check_theme_id();
+        let [h, s, v, a] = state.custom;
+        colorix.scales.custom = egui::ecolor::Hsva::new(h, s, v, a);
+        colorix.update_colors(ctx);
+        colorix
+    }
+
+    /// Returns the current theme, theme selection, theme preference and custom color.
+    /// The theme preference is read from the `Context`.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn state(&self, ctx: &egui::Context) -> ColorixState {
+        let egui::ecolor::Hsva { h, s, v, a } = self.scales.custom;
+        ColorixState {
+            theme: self.theme,
+            theme_id: self.theme_id,
+            theme_preference: ctx.options(|opt| opt.theme_preference),
+            custom: [h, s, v, a],
+        }
+    }
+
+    /// Save the state in the egui memory.
+    ///
+    /// NOTE: the memory is only written to disk if the `persistence` feature of egui (or eframe) is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl eframe::App for App {
+    ///     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    ///         self.colorix.save(ctx);
+    ///         // ..
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn save(&self, ctx: &egui::Context) {
+        let state = self.state(ctx);
+        ctx.data_mut(|data| data.insert_persisted(egui::Id::new(STATE_ID), state));
+    }
+
+    /// Restore a Colorix that was saved with [`Colorix::save`].
+    /// Falls back to [`Colorix::init`] with `theme` if nothing was saved.
+    #[cfg(feature = "serde")]
+    #[allow(clippy::must_use_candidate)]
+    pub fn load(ctx: &egui::Context, theme: Theme) -> Self {
+        let state =
+            ctx.data_mut(|data| data.get_persisted::<ColorixState>(egui::Id::new(STATE_ID)));
+        state.map_or_else(
+            || Self::init(ctx, theme),
+            |state| Self::init_with_state(ctx, state),
+        )
+    }
+
+    fn find_theme_id(&mut self) {
+        self.theme_id = self.registry.id_of(&self.theme);
+    }
+
+    /// Keeps the selection if the id still points to the same colors, otherwise looks the theme up
+    fn check_theme_id(&mut self) {
+        let selected = self.theme_id.and_then(|id| self.registry.get(id));
+        if selected.map(|named| named.theme) != Some(self.theme) {
+            self.find_theme_id();
+        }
+    }
+
+    /// The themes offered by [`Colorix::themes_dropdown`]
+    #[must_use]
+    pub const fn registry(&self) -> &ThemeRegistry {
+        &self.registry
+    }
+
+    /// Add or remove themes of the dropdown.
+    ///
+    /// NOTE: removing the selected theme keeps its colors, the dropdown shows it as "Custom".
+    pub fn registry_mut(&mut self) -> &mut ThemeRegistry {
+        &mut self.registry
+    }
+
+    /// Replace the themes offered by [`Colorix::themes_dropdown`], e.g. with only your own themes.
+    pub fn set_registry(&mut self, registry: ThemeRegistry) {
+        self.registry = registry;
+        self.check_theme_id();
+    }
+
+    /// The id of the selected theme, `None` if the current theme is not (or no longer) in the registry
+    #[must_use]
+    pub const fn theme_id(&self) -> Option<ThemeId> {
+        self.theme_id
+    }
+
+    /// Select a theme of the registry. Returns `false` if there is no theme with this id.
+    pub fn select_theme(&mut self, ctx: &egui::Context, id: ThemeId) -> bool {
+        let Some(named) = self.registry.get(id) else {
+            return false;
+        };
+        self.theme = named.theme;
+        self.theme_id = Some(id);
+        self.update_colors(ctx);
+        true
+    }
+    /// The tokens of either light or dark mode.
+    /// Both are computed at once and registered with egui, so egui's own theme switching works as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui::Theme;
+    /// use egui_colors::{utils, Colorix};
+    /// let ctx = egui::Context::default();
+    /// let colorix = Colorix::init(&ctx, utils::INDIGO_JADE);
+    /// let (light, dark) = (colorix.tokens_of(Theme::Light), colorix.tokens_of(Theme::Dark));
+    /// assert_ne!(light.app_background, dark.app_background);
+    /// assert_eq!(ctx.style_of(Theme::Light).visuals.panel_fill, light.subtle_background);
+    /// assert_eq!(ctx.style_of(Theme::Dark).visuals.panel_fill, dark.subtle_background);
+    /// ```
+    #[must_use]
+    pub const fn tokens_of(&self, theme: egui::Theme) -> &ColorTokens {
+        match theme {
+            egui::Theme::Light => &self.light_tokens,
+            egui::Theme::Dark => &self.dark_tokens,
+        }
+    }
+
+    /// Follow the light or dark mode egui is currently using, so `tokens` matches the active mode.
+    ///
+    /// The widgets of Colorix do this themselves. If none of them is shown,
+    /// call this once per frame to keep up with theme changes, e.g. when the operating system switches
+    /// while the theme preference is [`egui::ThemePreference::System`].
+    ///
+    /// This also drives the transition set with [`Colorix::set_transition_duration`].
+    pub fn sync_theme(&mut self, ctx: &egui::Context) {
+        self.synced = true;
+        let theme = ctx.theme();
+        let dark_mode = theme == egui::Theme::Dark;
+        if dark_mode != self.scales.dark_mode {
+            self.scales.dark_mode = dark_mode;
+            self.start_transition(ctx);
+        }
+        let target = *self.tokens_of(theme);
+        if let Some(transition) = self.transition {
+            let elapsed = (ctx.input(|i| i.time) - transition.start) as f32;
+            let t = elapsed / self.transition_duration;
+            if t < 1.0 {
+                let t = egui::emath::easing::cubic_in_out(t.max(0.0));
+                self.tokens = transition.from.lerp(&target, t);
+                if !self.scoped {
+                    ctx.set_visuals_of(theme, self.visuals_of_tokens(&self.tokens, dark_mode));
+                }
+                ctx.request_repaint();
+                return;
+            }
+            self.transition = None;
+            self.install_visuals(ctx);
+        }
+        self.tokens = target;
+    }
+
+    /// Animate color changes (of the theme and between light and dark mode) over `seconds`.
+    /// The default of 0 changes colors instantly.
+    ///
+    /// NOTE: the transition is driven by [`Colorix::sync_theme`], which the widgets of Colorix call.
+    /// Only a Colorix whose `sync_theme` is never called changes its colors instantly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{utils, Colorix};
+    /// let ctx = egui::Context::default();
+    /// let mut colorix = Colorix::init(&ctx, utils::EGUI_THEME);
+    /// colorix.set_transition_duration(0.5);
+    /// let _ = ctx.run(Default::default(), |ctx| colorix.sync_theme(ctx));
+    /// let id = colorix.registry().find("Warm").map(|t| t.id).unwrap();
+    /// colorix.select_theme(&ctx, id);
+    /// let target = colorix.visuals(ctx.theme());
+    /// // the active mode keeps its colors until `sync_theme` starts the fade
+    /// assert_ne!(ctx.style().visuals.selection.bg_fill, target.selection.bg_fill);
+    /// let mut frames = vec![];
+    /// for frame in 0..8 {
+    ///     let input = egui::RawInput { time: Some(f64::from(frame) * 0.1), ..Default::default() };
+    ///     let _ = ctx.run(input, |ctx| colorix.sync_theme(ctx));
+    ///     frames.push(ctx.style().visuals.selection.bg_fill);
+    /// }
+    /// // the colors fade in and end at the theme
+    /// assert_ne!(frames[2], target.selection.bg_fill);
+    /// assert_eq!(frames[7], target.selection.bg_fill);
+    ///
+    ///
+    /// // switching to dark mode halfway leaves the final light visuals for egui's own theme switch
+    /// let ctx = egui::Context::default();
+    /// ctx.set_theme(egui::Theme::Light);
+    /// let mut colorix = Colorix::init(&ctx, utils::EGUI_THEME);
+    /// colorix.set_transition_duration(0.5);
+    /// let _ = ctx.run(Default::default(), |ctx| colorix.sync_theme(ctx));
+    /// colorix.select_theme(&ctx, id);
+    /// for frame in 0..20 {
+    ///     if frame == 3 {
+    ///         ctx.set_theme(egui::Theme::Dark);
+    ///     }
+    ///     let input = egui::RawInput { time: Some(f64::from(frame) * 0.05), ..Default::default() };
+    ///     let _ = ctx.run(input, |ctx| colorix.sync_theme(ctx));
+    /// }
+    /// for theme in [egui::Theme::Light, egui::Theme::Dark] {
+    ///     assert_eq!(ctx.style_of(theme).visuals.selection.bg_fill, colorix.visuals(theme).selection.bg_fill);
+    /// }
+    ///
+    /// // without `sync_theme` the theme is applied at once
+    /// let mut colorix = Colorix::init(&ctx, utils::EGUI_THEME);
+    /// colorix.set_transition_duration(0.5);
+    /// let id = colorix.registry().find("Warm").map(|t| t.id).unwrap();
+    /// colorix.select_theme(&ctx, id);
+    /// let target = colorix.visuals(ctx.theme());
+    /// assert_eq!(ctx.style().visuals.selection.bg_fill, target.selection.bg_fill);
+    /// ```
+    pub fn set_transition_duration(&mut self, seconds: f32) {
+        self.transition_duration = seconds.max(0.0);
+    }
+
+    fn start_transition(&mut self, ctx: &egui::Context) {
+        if self.transition_duration > 0.0 && self.synced {
+            // a superseded transition may have left intermediate colors in either mode
+            if self.transition.is_some() {
+                self.install_visuals(ctx);
             }
+            self.transition = Some(Transition {
+                from: self.tokens,
+                start: ctx.input(|i| i.time),
+            });
+            ctx.request_repaint();
+        }
+    }
+
+    /// Registers the final visuals of both modes with egui.
+    fn install_visuals(&self, ctx: &egui::Context) {
+        if !self.scoped {
+            ctx.set_visuals_of(egui::Theme::Light, self.visuals(egui::Theme::Light));
+            ctx.set_visuals_of(egui::Theme::Dark, self.visuals(egui::Theme::Dark));
+        }
+    }
+
+    /// Set the theme preference of egui to light, dark or following the operating system.
+    pub fn set_theme_preference(
+        &mut self,
+        ctx: &egui::Context,
+        theme_preference: egui::ThemePreference,
+    ) {
+        ctx.set_theme(theme_preference);
+        self.sync_theme(ctx);
+    }
+
+    /// Switch between light and dark mode.
+    pub fn light_dark_toggle_button(&mut self, ui: &mut egui::Ui) {
+        self.sync_theme(ui.ctx());
+        let (icon, hover_text, theme) = if self.scales.dark_mode {
+            ("☀", "Switch to light mode", egui::Theme::Light)
         } else {
-            if ui
-                .add(
-                    egui::Button::new(egui::RichText::new("🌙").size(20.))
-                        .min_size(egui::Vec2::new(30., 30.))
-                        .frame(false),
+            ("🌙", "Switch to dark mode", egui::Theme::Dark)
+        };
+        if ui
+            .add(
+                egui::Button::new(egui::RichText::new(icon).size(20.))
+                    .min_size(egui::Vec2::new(30., 30.))
+                    .frame(false),
+            )
+            .on_hover_text(hover_text)
+            .clicked()
+        {
+            ui.ctx().set_theme(theme);
+            self.sync_theme(ui.ctx());
+        }
+    }
+
+    /// Cycle between light mode, dark mode and following the operating system.
+    pub fn light_dark_system_toggle_button(&mut self, ui: &mut egui::Ui) {
+        self.sync_theme(ui.ctx());
+        let theme_preference = ui.ctx().options(|opt| opt.theme_preference);
+        let (icon, hover_text, next) = match theme_preference {
+            egui::ThemePreference::Light => (
+                "☀",
+                "Light mode. Switch to dark mode".to_owned(),
+                egui::ThemePreference::Dark,
+            ),
+            egui::ThemePreference::Dark => (
+                "🌙",
+                "Dark mode. Switch to following the system".to_owned(),
+                egui::ThemePreference::System,
+            ),
+            egui::ThemePreference::System => {
+                let system = match ui.ctx().system_theme() {
+                    Some(egui::Theme::Dark) => "dark",
+                    Some(egui::Theme::Light) => "light",
+                    None => "unknown",
+                };
+                (
+                    "💻",
+                    format!("Following the system ({system}). Switch to light mode"),
+                    egui::ThemePreference::Light,
                 )
-                .on_hover_text("Switch to dark mode")
-                .clicked()
-            {
-                self.scales.dark_mode = true;
-                ui.ctx().set_visuals(egui::Visuals {
-                    dark_mode: true,
-                    ..Default::default()
-                });
-                self.update_colors(ui.ctx());
             }
+        };
+        if ui
+            .add(
+                egui::Button::new(egui::RichText::new(icon).size(20.))
+                    .min_size(egui::Vec2::new(30., 30.))
+                    .frame(false),
+            )
+            .on_hover_text(hover_text)
+            .clicked()
+        {
+            self.set_theme_preference(ui.ctx(), next);
         }
     }
 
-    /// Choose from a list of preset themes. It is possible to add custom themes.
+    /// Choose from a list of preset themes. It is possible to add custom themes to the registry.
     /// NOTE: custom values chosen without the custom color picker are not recommended!
     ///
     /// # Examples
     ///
     /// ```ignore
-    /// use egui_colors::tokens::ThemeColor;
-    /// let names = vec!["YellowGreen"];
-    /// let themes = vec![[ThemeColor::Custom([178, 194, 31]); 12]];
-    /// let custom = Some((names, themes));
+    /// use egui_colors::{registry::ThemeRegistry, tokens::ThemeColor};
+    /// app.colorix
+    ///     .registry_mut()
+    ///     .add("YellowGreen", [ThemeColor::Custom([178, 194, 31]); 12]);
     ///
-    /// // if you want to display custom themes only, set `custom_only` to `true`
-    /// app.colorix.themes_dropdown(ctx, ui, custom, false);
+    /// // if you want to display custom themes only, start from an empty registry
+    /// let mut registry = ThemeRegistry::new();
+    /// registry.add("YellowGreen", [ThemeColor::Custom([178, 194, 31]); 12]);
+    /// app.colorix.set_registry(registry);
+    ///
+    /// app.colorix.themes_dropdown(ui);
     /// ```
-    pub fn themes_dropdown(
-        &mut self,
-        ui: &mut egui::Ui,
-        custom_themes: Option<(Vec<&str>, Vec<Theme>)>,
-        custom_only: bool,
-    ) {
-        let combi_themes: Vec<Theme>;
-        let combi_names: Vec<&str>;
-
-        if let Some(custom) = custom_themes {
-            let (names, themes) = custom;
-            if custom_only {
-                combi_themes = themes;
-                combi_names = names;
-            } else {
-                combi_themes = THEMES.iter().copied().chain(themes).collect();
-                combi_names = THEME_NAMES.iter().copied().chain(names).collect();
-            }
-        } else {
-            combi_names = THEME_NAMES.to_vec();
-            combi_themes = THEMES.to_vec();
-        }
+    pub fn themes_dropdown(&mut self, ui: &mut egui::Ui) {
+        // the registry may have changed with `registry_mut`
+        self.check_theme_id();
+        let selected = self
+            .theme_id
+            .and_then(|id| self.registry.get(id))
+            .map_or("Custom", |named| named.name.as_str());
+        let mut clicked = None;
         egui::ComboBox::from_id_salt("Select Theme")
-            .selected_text(combi_names[self.theme_index])
+            .selected_text(selected)
             .show_ui(ui, |ui| {
-                for i in 0..combi_themes.len() {
+                for named in self.registry.iter() {
                     if ui
-                        .selectable_value(&mut self.theme, combi_themes[i], combi_names[i])
+                        .selectable_label(self.theme_id == Some(named.id), &named.name)
                         .clicked()
                     {
-                        self.theme_index = i;
-                        self.update_colors(ui.ctx());
-                    };
+                        clicked = Some(named.id);
+                    }
                 }
             });
+        if let Some(id) = clicked {
+            self.select_theme(ui.ctx(), id);
+        }
     }
     /// A widget with 12 dropdown menus of the UI elements (`ColorTokens`) that can be set.
+    /// Themes can be copied to the clipboard, and pasted back either as copied
+    /// or as a list of 12 hex colors (see [`parse::theme_from_str`]).
     pub fn ui_combo_12(&mut self, ui: &mut egui::Ui) {
-        let dropdown_colors: [ThemeColor; 23] = [
-            ThemeColor::Gray,
-            ThemeColor::EguiBlue,
-            ThemeColor::Tomato,
-            ThemeColor::Red,
-            ThemeColor::Ruby,
-            ThemeColor::Crimson,
-            ThemeColor::Pink,
-            ThemeColor::Plum,
-            ThemeColor::Purple,
-            ThemeColor::Violet,
-            ThemeColor::Iris,
-            ThemeColor::Indigo,
-            ThemeColor::Blue,
-            ThemeColor::Cyan,
-            ThemeColor::Teal,
-            ThemeColor::Jade,
-            ThemeColor::Green,
-            ThemeColor::Grass,
-            ThemeColor::Brown,
-            ThemeColor::Bronze,
-            ThemeColor::Gold,
-            ThemeColor::Orange,
-            ThemeColor::Custom(self.scales.custom()),
-        ];
+        self.sync_theme(ui.ctx());
+        let dropdown_colors = PRESETS
+            .iter()
+            .copied()
+            .chain([ThemeColor::Custom(self.scales.custom())]);
         ui.vertical(|ui| {
             for (i, label) in LABELS.iter().enumerate() {
                 ui.horizontal(|ui| {
@@ -217,13 +689,13 @@ impl Colorix {
                     egui::ComboBox::from_label(*label)
                         .selected_text(self.theme[i].label())
                         .show_ui(ui, |ui| {
-                            for preset in dropdown_colors {
+                            for preset in dropdown_colors.clone() {
                                 if ui
                                     .selectable_value(&mut self.theme[i], preset, preset.label())
                                     .clicked()
                                 {
                                     self.update_color(ui.ctx(), i);
-                                };
+                                }
                             }
                         });
                 });
@@ -232,58 +704,84 @@ impl Colorix {
             if ui.button("Copy theme to clipboard").clicked() {
                 ui.output_mut(|out| out.copied_text = format!("{:#?}", self.theme));
             }
-        });
-    }
-
-    fn process_theme(&mut self) {
-        let mut processed: Vec<usize> = vec![];
-        for (i, v) in self.theme.iter().enumerate() {
-            if !processed.contains(&i) {
-                self.scales.process_color(*v);
-                self.tokens.update_schema(i, self.scales.scale[i]);
-                if i < self.theme.len() {
-                    for (j, w) in self.theme[i + 1..].iter().enumerate() {
-                        if w == v {
-                            self.tokens
-                                .update_schema(j + i + 1, self.scales.scale[j + i + 1]);
-                            processed.push(j + i + 1);
-                        }
+            let re = ui.add(
+                egui::TextEdit::singleline(&mut self.pasted_theme)
+                    .hint_text("Paste theme from clipboard"),
+            );
+            if re.changed() && self.pasted_theme.trim().is_empty() {
+                self.paste_error = None;
+            } else if re.changed() {
+                match parse::theme_from_str(&self.pasted_theme) {
+                    Ok(theme) => {
+                        self.theme = theme;
+                        self.find_theme_id();
+                        self.update_colors(ui.ctx());
+                        self.pasted_theme.clear();
+                        self.paste_error = None;
                     }
+                    Err(err) => self.paste_error = Some(err),
                 }
             }
-        }
+            if let Some(err) = &self.paste_error {
+                ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+            }
+        });
     }
 
-    // pub fn process_2nd_theme(&mut self, theme: &[ThemeColor; 12]) {
-    //     let mut processed: Vec<usize> = vec![];
-    //     for (i, v) in theme.iter().enumerate() {
-    //         if !processed.contains(&i) {
-    //             self.scales.process_color(v);
-    //             self.tokens2.update_scheme(i, self.scales.scale[i]);
-    //             if i < self.theme.len() {
-    //                 for (j, w) in self.theme[i + 1..].iter().enumerate() {
-    //                     if w == v {
-    //                         self.tokens2.update_scheme(j + i + 1, self.scales.scale[j + i + 1]);
-    //                         processed.push(j + i + 1)
-    //                     }
-    //                 }
-    //             }
-    //         }
-    //     }
-    //     self.tokens.text_color();
-    // }
-
     fn update_color(&mut self, ctx: &egui::Context, i: usize) {
-        self.scales.process_color(self.theme[i]);
-        self.tokens.update_schema(i, self.scales.scale[i]);
-        self.tokens.color_on_accent();
-        self.tokens.set_egui_visuals(ctx);
+        self.find_theme_id();
+        // the high contrast tokens depend on several steps, and all alpha colors on the app background
+        if self.high_contrast || i == 0 {
+            self.update_colors(ctx);
+            return;
+        }
+        for (tokens, dark_mode) in [
+            (&mut self.light_tokens, false),
+            (&mut self.dark_tokens, true),
+        ] {
+            let scale = Scale::with_model(self.theme[i], dark_mode, self.scales.contrast_model);
+            tokens.update_schema(i, scale.colors[i]);
+            tokens.color_on_accent(self.scales.contrast_model);
+            tokens.update_alpha();
+            tokens.alpha_scales[i] = scale.alpha(tokens.app_background);
+        }
+        self.set_egui_visuals(ctx);
     }
 
     fn update_colors(&mut self, ctx: &egui::Context) {
-        self.process_theme();
-        self.tokens.color_on_accent();
-        self.tokens.set_egui_visuals(ctx);
+        self.light_tokens = self.compute_tokens(false);
+        self.dark_tokens = self.compute_tokens(true);
+        self.set_egui_visuals(ctx);
+    }
+
+    fn compute_tokens(&self, dark_mode: bool) -> ColorTokens {
+        let mut tokens =
+            ColorTokens::from_theme(&self.theme, dark_mode, self.scales.contrast_model);
+        tokens.semantic =
+            SemanticTokens::new(&self.semantic_colors, dark_mode, self.scales.contrast_model);
+        if self.high_contrast {
+            tokens = tokens.high_contrast(dark_mode, self.scales.contrast_model);
+        }
+        tokens
+    }
+
+    fn set_egui_visuals(&mut self, ctx: &egui::Context) {
+        self.start_transition(ctx);
+        self.install_visuals(ctx);
+        let theme = ctx.theme();
+        self.scales.dark_mode = theme == egui::Theme::Dark;
+        if let Some(transition) = self.transition {
+            // the active mode keeps its colors for the rest of this frame, `sync_theme` takes it from there
+            self.tokens = transition.from;
+            if !self.scoped {
+                ctx.set_visuals_of(
+                    theme,
+                    self.visuals_of_tokens(&self.tokens, self.scales.dark_mode),
+                );
+            }
+        } else {
+            self.tokens = *self.tokens_of(theme);
+        }
     }
 
     /// NOTE: values are clamped for useability.
@@ -297,12 +795,13 @@ impl Colorix {
         .changed()
         {
             self.scales.clamp_custom();
-        };
+        }
     }
 
     /// Set a background gradient. Choose 'true' for color from `solid_backgrounds`
     /// and 'false' for`ui_element_background`
     pub fn draw_background(&mut self, ctx: &egui::Context, accent: bool) {
+        self.sync_theme(ctx);
         let bg = if accent {
             self.scales.process_color(self.theme[8]);
             self.scales.scale[2]
@@ -321,6 +820,193 @@ impl Colorix {
         mesh.add_triangle(1, 2, 3);
         painter.add(egui::Shape::Mesh(mesh));
     }
+    /// Set the colors of the semantic roles (error, warning, success and info).
+    /// Errors and warnings are used for egui's `error_fg_color` and `warn_fg_color`,
+    /// all scales are available in `tokens.semantic`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{tokens::{SemanticColors, ThemeColor}, utils, Colorix};
+    /// let ctx = egui::Context::default();
+    /// let mut colorix = Colorix::init(&ctx, utils::EGUI_THEME);
+    /// let colors = SemanticColors { error: ThemeColor::Crimson, ..Default::default() };
+    /// colorix.set_semantic_colors(&ctx, colors);
+    /// assert_eq!(ctx.style().visuals.error_fg_color, colorix.tokens.semantic.error[10]);
+    /// ```
+    pub fn set_semantic_colors(&mut self, ctx: &egui::Context, semantic_colors: SemanticColors) {
+        self.semantic_colors = semantic_colors;
+        self.update_colors(ctx);
+    }
+
+    /// Returns the colors of the semantic roles
+    #[must_use]
+    pub const fn semantic_colors(&self) -> &SemanticColors {
+        &self.semantic_colors
+    }
+
+    /// Set the contrast algorithm used for the color on accents and to adjust the scales.
+    /// Use [`ContrastModel::Wcag`] to target the WCAG 2.x ratios (4.5:1 for text on accents).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{registry::ThemeRegistry, wcag, Colorix, ContrastModel};
+    /// let ctx = egui::Context::default();
+    /// for named in ThemeRegistry::default().iter() {
+    ///     let mut colorix = Colorix::init(&ctx, named.theme);
+    ///     colorix.set_contrast_model(&ctx, ContrastModel::Wcag);
+    ///     for mode in [egui::Theme::Light, egui::Theme::Dark] {
+    ///         let tokens = colorix.tokens_of(mode);
+    ///         let ratio = wcag::contrast_ratio(tokens.on_accent, tokens.solid_backgrounds);
+    ///         assert!(ratio >= wcag::AA_TEXT, "{} in {mode:?}: {ratio:.2}", named.name);
+    ///     }
+    /// }
+    /// ```
+    pub fn set_contrast_model(&mut self, ctx: &egui::Context, contrast_model: ContrastModel) {
+        self.scales.contrast_model = contrast_model;
+        self.update_colors(ctx);
+    }
+
+    /// Returns the contrast algorithm
+    #[must_use]
+    pub const fn contrast_model(&self) -> ContrastModel {
+        self.scales.contrast_model
+    }
+
+    /// Checks the contrast of the text, accent and border tokens of the active mode,
+    /// with the contrast model set by [`Colorix::set_contrast_model`].
+    #[must_use]
+    pub fn contrast_report(&self) -> audit::ContrastReport {
+        let theme = if self.scales.dark_mode {
+            egui::Theme::Dark
+        } else {
+            egui::Theme::Light
+        };
+        audit::contrast_report(self.tokens_of(theme), self.scales.contrast_model)
+    }
+
+    /// A 12×12 grid with every token as text on every token as background, with its Lc or WCAG ratio.
+    /// The border shows if the pair is readable as text (success color),
+    /// only as a non-text element (warning color) or not at all (error color).
+    pub fn contrast_matrix(&mut self, ui: &mut egui::Ui) {
+        self.sync_theme(ui.ctx());
+        let tokens = self.tokens;
+        let model = self.scales.contrast_model;
+        let cell_size = egui::vec2(44.0, 30.0);
+        egui::Grid::new("egui_colors_contrast_matrix")
+            .spacing(egui::vec2(2.0, 2.0))
+            .show(ui, |ui| {
+                ui.label("bg \\ fg");
+                for (i, label) in LABELS.iter().enumerate() {
+                    ui.label((i + 1).to_string()).on_hover_text(*label);
+                }
+                ui.end_row();
+                for (bg, bg_label) in LABELS.iter().enumerate() {
+                    ui.label((bg + 1).to_string()).on_hover_text(*bg_label);
+                    for (fg, fg_label) in LABELS.iter().enumerate() {
+                        let (foreground, background) = (tokens.get_token(fg), tokens.get_token(bg));
+                        let lc = apca::estimate_lc(foreground, background);
+                        let ratio = wcag::contrast_ratio(foreground, background);
+                        let border = if audit::ContrastLevel::Text.is_met(model, lc, ratio) {
+                            tokens.semantic.success[8]
+                        } else if audit::ContrastLevel::NonText.is_met(model, lc, ratio) {
+                            tokens.semantic.warning[8]
+                        } else {
+                            tokens.semantic.error[8]
+                        };
+                        let value = match model {
+                            ContrastModel::Apca => format!("{lc:.0}"),
+                            ContrastModel::Wcag => format!("{ratio:.1}"),
+                        };
+                        let (rect, response) =
+                            ui.allocate_exact_size(cell_size, egui::Sense::hover());
+                        let painter = ui.painter();
+                        painter.rect(rect, 2.0, background, egui::Stroke::new(2.0, border));
+                        painter.text(
+                            rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            format!("Aa {value}"),
+                            egui::FontId::proportional(11.0),
+                            foreground,
+                        );
+                        response.on_hover_text(format!(
+                            "{fg_label} on {bg_label}\nLc {lc:.1}, {ratio:.2}:1"
+                        ));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Switch to the high contrast variant of the theme (in both modes), see [`ColorTokens::high_contrast`].
+    pub fn set_high_contrast(&mut self, ctx: &egui::Context, high_contrast: bool) {
+        self.high_contrast = high_contrast;
+        self.update_colors(ctx);
+    }
+
+    /// Returns whether the high contrast variant is used
+    #[must_use]
+    pub const fn high_contrast(&self) -> bool {
+        self.high_contrast
+    }
+
+    /// Preview the visuals as seen with a color vision deficiency, `None` shows the real colors.
+    /// The tokens are not changed, use [`ColorTokens::simulate`] for custom painting.
+    pub fn set_cvd_preview(&mut self, ctx: &egui::Context, preview: Option<ColorBlindness>) {
+        self.cvd_preview = preview;
+        self.set_egui_visuals(ctx);
+    }
+
+    /// Returns the simulated color vision deficiency, if any
+    #[must_use]
+    pub const fn cvd_preview(&self) -> Option<ColorBlindness> {
+        self.cvd_preview
+    }
+
+    /// A dropdown to preview the visuals with a color vision deficiency.
+    pub fn cvd_preview_dropdown(&mut self, ui: &mut egui::Ui) {
+        let mut preview = self.cvd_preview;
+        egui::ComboBox::from_label("Color vision")
+            .selected_text(preview.map_or("Normal", ColorBlindness::label))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut preview, None, "Normal");
+                for color_blindness in ColorBlindness::ALL {
+                    ui.selectable_value(
+                        &mut preview,
+                        Some(color_blindness),
+                        color_blindness.label(),
+                    );
+                }
+            });
+        if preview != self.cvd_preview {
+            self.set_cvd_preview(ui.ctx(), preview);
+        }
+    }
+
+    /// Set the rounding, stroke widths, expansions and shadows,
+    /// e.g. one of the presets [`StyleShape::SHARP`], [`StyleShape::SOFT`] or [`StyleShape::PILL`].
+    pub fn set_shape(&mut self, ctx: &egui::Context, shape: StyleShape) {
+        self.shape = shape;
+        self.set_egui_visuals(ctx);
+    }
+
+    /// Returns the rounding, stroke widths, expansions and shadows
+    #[must_use]
+    pub const fn shape(&self) -> &StyleShape {
+        &self.shape
+    }
+
+    /// Set which token feeds which field of the egui visuals. See [`VisualsMapping`].
+    pub fn set_visuals_mapping(
+        &mut self,
+        ctx: &egui::Context,
+        mapping: impl VisualsMapping + 'static,
+    ) {
+        self.visuals_mapping = Some(Arc::new(mapping));
+        self.set_egui_visuals(ctx);
+    }
+
     /// Returns the currently set theme
     #[must_use]
     pub const fn theme(&self) -> &Theme {
@@ -0,0 +1,360 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    builder::ThemeBuilder,
+    parse::ParseThemeError,
+    registry::{ThemeId, ThemeRegistry},
+    tokens::ThemeColor,
+    Colorix, Theme,
+};
+
+/// The contents of a theme file. The name defaults to the file name without extension.
+///
+/// ```toml
+/// name = "Yellow"
+/// colors = ["Gray", "Gray", "#8c958a", "Custom([232, 210, 7])", ...]
+/// ```
+///
+/// or a registered theme with overrides:
+///
+/// ```toml
+/// name = "Indigo/crimson"
+/// base = "Indigo/jade"
+/// [overrides]
+/// solid_backgrounds = "Crimson"
+/// ```
+#[derive(serde::Deserialize)]
+struct ThemeFile {
+    name: Option<String>,
+    base: Option<String>,
+    colors: Option<Vec<String>>,
+    #[serde(default)]
+    overrides: BTreeMap<String, String>,
+}
+
+/// The file formats of theme files, chosen by extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeFormat {
+    Toml,
+    Json,
+    Ron,
+}
+
+impl ThemeFormat {
+    /// The format of a file with this extension, `None` if it is not a theme file.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "ron" => Some(Self::Ron),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the name (if given) and colors of a theme file.
+/// Colors are written as in [`crate::parse::theme_from_debug`]: a preset name, `Custom([r, g, b])` or `#rrggbb`.
+///
+/// Instead of `colors` a file can name a `base` theme of `registry`, and both can be changed
+/// per token with `overrides` (see [`ThemeBuilder`]).
+///
+/// # Errors
+///
+/// Returns a [`ParseThemeError`] if the text is not valid in `format`, a color or token is invalid,
+/// the base theme is unknown or the theme doesn't have 12 colors.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::loader::{parse_theme_file, ThemeFormat};
+/// let text = r##"
+/// name = "Yellow"
+/// colors = ["Gray", "Gray", "Gray", "Gray", "Gray", "Gray",
+///           "Gray", "Gray", "#e8d207", "#e8d207", "Gray", "Gray"]
+/// "##;
+/// let registry = egui_colors::registry::ThemeRegistry::default();
+/// let (name, _theme) = parse_theme_file(text, ThemeFormat::Toml, &registry).unwrap();
+/// assert_eq!(name.as_deref(), Some("Yellow"));
+/// ```
+pub fn parse_theme_file(
+    text: &str,
+    format: ThemeFormat,
+    registry: &ThemeRegistry,
+) -> Result<(Option<String>, Theme), ParseThemeError> {
+    let invalid = |err: &dyn fmt::Display| ParseThemeError::InvalidFormat(err.to_string());
+    let file: ThemeFile = match format {
+        ThemeFormat::Toml => toml::from_str(text).map_err(|err| invalid(&err))?,
+        ThemeFormat::Json => serde_json::from_str(text).map_err(|err| invalid(&err))?,
+        ThemeFormat::Ron => ron::from_str(text).map_err(|err| invalid(&err))?,
+    };
+    let base = match (file.colors, file.base) {
+        (Some(colors), None) => {
+            let colors = colors
+                .iter()
+                .map(|color| color.parse::<ThemeColor>())
+                .collect::<Result<Vec<_>, _>>()?;
+            let len = colors.len();
+            Theme::try_from(colors).map_err(|_| ParseThemeError::WrongLength(len))?
+        }
+        (None, Some(base)) => {
+            registry
+                .find(&base)
+                .ok_or(ParseThemeError::UnknownTheme(base))?
+                .theme
+        }
+        _ => {
+            return Err(ParseThemeError::InvalidFormat(
+                "a theme file needs either 'colors' or 'base'".to_owned(),
+            ))
+        }
+    };
+    let theme = file
+        .overrides
+        .iter()
+        .fold(ThemeBuilder::new(base), |builder, (token, color)| {
+            builder.set_str(token, color)
+        })
+        .build()?;
+    Ok((file.name, theme))
+}
+
+/// The error returned when a theme file or directory can't be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadThemeError {
+    /// The file or directory can't be read
+    Io(PathBuf, String),
+    /// The file is not a valid theme
+    Parse(PathBuf, ParseThemeError),
+    /// The name of the theme is already used by a predefined theme, a theme of another file
+    /// or a theme added with [`ThemeRegistry::add`]
+    NameTaken(PathBuf, String),
+}
+
+impl fmt::Display for LoadThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Parse(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::NameTaken(path, name) => {
+                write!(
+                    f,
+                    "{}: there is already a theme named '{name}'",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadThemeError {}
+
+#[derive(Debug, Clone)]
+struct LoadedFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    /// The registry entry this file created, the loader never touches other entries
+    id: Option<ThemeId>,
+    /// The error of the last read, already reported
+    failed: Option<LoadThemeError>,
+}
+
+/// Polling more often only costs time, a file is not written much faster than this.
+const MIN_POLL_INTERVAL: f32 = 0.1;
+
+/// A file that failed is read again while its modification time is this recent,
+/// as it may still be being written within the same timer tick.
+const RETRY_WINDOW: Duration = Duration::from_secs(2);
+
+/// A theme whose file changed: the id before (if it was loaded before) and after the change.
+type Changed = (Option<ThemeId>, ThemeId);
+
+/// Loads the theme files (`.toml`, `.json` and `.ron`, see [`parse_theme_file`]) of a directory
+/// into a [`ThemeRegistry`], and reloads them when they change.
+///
+/// Files are loaded in alphabetical order, so a `base` can be a predefined theme or a file that comes earlier.
+/// A theme with a base is not reloaded when only the file of its base changes.
+/// The loader only adds, replaces and removes the themes of its own files: a theme named like a
+/// predefined theme or like the theme of an earlier file is rejected with [`LoadThemeError::NameTaken`],
+/// and a loaded theme renamed to such a name is removed.
+///
+/// Each error is returned once. A file that failed is read again when it changes, or on every
+/// poll for two seconds after it was modified, so a file saved in two writes is loaded when complete.
+///
+/// # Examples
+///
+/// ```ignore
+/// // in App::new
+/// let mut loader = ThemeLoader::new("themes");
+/// // in App::update, polls the files once a second
+/// for err in loader.poll(ctx, &mut self.colorix) {
+///     eprintln!("{err}");
+/// }
+/// self.colorix.themes_dropdown(ui);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThemeLoader {
+    dir: PathBuf,
+    files: Vec<LoadedFile>,
+    poll_interval: f32,
+    last_poll: Option<f64>,
+}
+
+impl ThemeLoader {
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            files: vec![],
+            poll_interval: 1.0,
+            last_poll: None,
+        }
+    }
+
+    /// How often [`ThemeLoader::poll`] checks the files, in seconds. The default is 1, the minimum 0.1.
+    pub fn set_poll_interval(&mut self, seconds: f32) {
+        self.poll_interval = seconds.max(MIN_POLL_INTERVAL);
+    }
+
+    /// Adds the themes of new and changed files to `registry` and removes those of deleted files.
+    /// Only files that changed since the last call are read.
+    pub fn load(&mut self, registry: &mut ThemeRegistry) -> Vec<LoadThemeError> {
+        self.scan(registry).1
+    }
+
+    /// Call once per frame: checks the files every poll interval and reloads the changed themes
+    /// into the registry of `colorix`. If the file of the active theme changed, its colors are applied.
+    pub fn poll(&mut self, ctx: &egui::Context, colorix: &mut Colorix) -> Vec<LoadThemeError> {
+        let now = ctx.input(|i| i.time);
+        let interval = f64::from(self.poll_interval);
+        if let Some(last_poll) = self.last_poll {
+            if now - last_poll < interval {
+                ctx.request_repaint_after_secs((interval - (now - last_poll)) as f32);
+                return vec![];
+            }
+        }
+        self.last_poll = Some(now);
+        ctx.request_repaint_after_secs(self.poll_interval);
+        let (changed, errors) = self.scan(colorix.registry_mut());
+        for (old, new) in changed {
+            let active = colorix.theme_id();
+            if active == Some(new) || (old.is_some() && active == old) {
+                colorix.select_theme(ctx, new);
+            }
+        }
+        errors
+    }
+
+    fn scan(&mut self, registry: &mut ThemeRegistry) -> (Vec<Changed>, Vec<LoadThemeError>) {
+        let mut changed = vec![];
+        let mut errors = vec![];
+        let mut paths: Vec<PathBuf> = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| ThemeFormat::from_path(path).is_some())
+                .collect(),
+            Err(err) => {
+                errors.push(LoadThemeError::Io(self.dir.clone(), err.to_string()));
+                return (changed, errors);
+            }
+        };
+        paths.sort();
+        // deleted files
+        self.files.retain(|file| {
+            let exists = paths.contains(&file.path);
+            if !exists {
+                if let Some(id) = file.id {
+                    registry.remove(id);
+                }
+            }
+            exists
+        });
+        for path in paths {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            let i = if let Some(i) = self.files.iter().position(|file| file.path == path) {
+                let file = &self.files[i];
+                if file.modified == modified && modified.is_some() {
+                    let recent = modified.is_some_and(|modified| {
+                        SystemTime::now()
+                            .duration_since(modified)
+                            .map_or(true, |age| age < RETRY_WINDOW)
+                    });
+                    if file.failed.is_none() || !recent {
+                        continue;
+                    }
+                }
+                i
+            } else {
+                self.files.push(LoadedFile {
+                    path: path.clone(),
+                    modified: None,
+                    id: None,
+                    failed: None,
+                });
+                self.files.len() - 1
+            };
+            let file = &mut self.files[i];
+            let result = load_file(&path, registry).and_then(|(name, theme)| {
+                if registry
+                    .find(&name)
+                    .is_some_and(|named| Some(named.id) != file.id)
+                {
+                    Err(LoadThemeError::NameTaken(path.clone(), name))
+                } else {
+                    Ok((name, theme))
+                }
+            });
+            let retried = file.modified == modified;
+            file.modified = modified;
+            match result {
+                Ok((name, theme)) => {
+                    let old = file.id;
+                    // a renamed theme replaces the old entry
+                    if let Some(id) = old {
+                        if registry.get(id).is_some_and(|named| named.name != name) {
+                            registry.remove(id);
+                        }
+                    }
+                    let id = registry.add(name, theme);
+                    file.id = Some(id);
+                    file.failed = None;
+                    changed.push((old, id));
+                }
+                Err(err) => {
+                    // a theme renamed to a taken name is removed, like a deleted file
+                    if let LoadThemeError::NameTaken(..) = err {
+                        if let Some(id) = file.id.take() {
+                            registry.remove(id);
+                        }
+                    }
+                    // a file is read again while it may be half written, but its error is reported once
+                    if !(retried && file.failed.as_ref() == Some(&err)) {
+                        errors.push(err.clone());
+                    }
+                    file.failed = Some(err);
+                }
+            }
+        }
+        (changed, errors)
+    }
+}
+
+fn load_file(path: &Path, registry: &ThemeRegistry) -> Result<(String, Theme), LoadThemeError> {
+    let format = ThemeFormat::from_path(path).unwrap_or(ThemeFormat::Toml);
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| LoadThemeError::Io(path.to_owned(), err.to_string()))?;
+    let (name, theme) = parse_theme_file(&text, format, registry)
+        .map_err(|err| LoadThemeError::Parse(path.to_owned(), err))?;
+    let name = name.unwrap_or_else(|| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+    Ok((name, theme))
+}
@@ -0,0 +1,145 @@
+use egui::{
+    style::{TextCursorStyle, WidgetVisuals},
+    Color32, Rounding, Stroke,
+};
+
+use crate::{shape::StyleShape, tokens::ColorTokens};
+
+/// Decides which token feeds which field of the egui visuals.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{mapping::{DefaultMapping, VisualsMapping}, shape::StyleShape, tokens::ColorTokens};
+///
+/// // borders on inactive buttons and a different hyperlink color
+/// #[derive(Debug)]
+/// struct MyMapping;
+///
+/// impl VisualsMapping for MyMapping {
+///     fn apply(&self, tokens: &ColorTokens, shape: &StyleShape, visuals: &mut egui::Visuals) {
+///         DefaultMapping.apply(tokens, shape, visuals);
+///         visuals.widgets.inactive.bg_stroke.color = tokens.ui_element_border_and_focus_rings;
+///         visuals.hyperlink_color = tokens.low_contrast_text;
+///     }
+/// }
+///
+/// let ctx = egui::Context::default();
+/// let mut colorix = egui_colors::Colorix::init(&ctx, egui_colors::utils::EGUI_THEME);
+/// colorix.set_visuals_mapping(&ctx, MyMapping);
+/// let visuals = ctx.style().visuals.clone();
+/// assert_eq!(visuals.hyperlink_color, colorix.tokens.low_contrast_text);
+/// assert_eq!(
+///     visuals.widgets.inactive.bg_stroke.color,
+///     colorix.tokens.ui_element_border_and_focus_rings
+/// );
+/// ```
+pub trait VisualsMapping: std::fmt::Debug + Send + Sync {
+    /// Set the fields of `visuals`, which start out as the default light or dark visuals of egui.
+    fn apply(&self, tokens: &ColorTokens, shape: &StyleShape, visuals: &mut egui::Visuals);
+}
+
+/// The mapping that is used unless another one is set with [`crate::Colorix::set_visuals_mapping`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultMapping;
+
+impl VisualsMapping for DefaultMapping {
+    fn apply(&self, tokens: &ColorTokens, shape: &StyleShape, visuals: &mut egui::Visuals) {
+        let selection = egui::style::Selection {
+            bg_fill: tokens.solid_backgrounds,
+            stroke: Stroke::new(1.0, tokens.on_accent),
+        };
+        let text_cursor = TextCursorStyle {
+            stroke: Stroke::new(2.0, tokens.low_contrast_text),
+            ..Default::default()
+        };
+        let widgets = egui::style::Widgets {
+            noninteractive: WidgetVisuals {
+                weak_bg_fill: tokens.subtle_background,
+                bg_fill: tokens.subtle_background,
+                bg_stroke: Stroke::new(
+                    shape.noninteractive.bg_stroke_width,
+                    tokens.subtle_borders_and_separators,
+                ), // separators, indentation lines
+                fg_stroke: Stroke::new(
+                    shape.noninteractive.fg_stroke_width,
+                    tokens.low_contrast_text,
+                ), // normal text color
+                rounding: Rounding::same(shape.noninteractive.rounding),
+                expansion: shape.noninteractive.expansion,
+            },
+            inactive: WidgetVisuals {
+                weak_bg_fill: tokens.ui_element_background, // button background
+                bg_fill: tokens.ui_element_background,      // checkbox background
+                bg_stroke: Stroke::new(
+                    shape.inactive.bg_stroke_width,
+                    tokens.ui_element_background,
+                ),
+                fg_stroke: Stroke::new(shape.inactive.fg_stroke_width, tokens.low_contrast_text), // button text
+                rounding: Rounding::same(shape.inactive.rounding),
+                expansion: shape.inactive.expansion,
+            },
+            hovered: WidgetVisuals {
+                weak_bg_fill: tokens.hovered_ui_element_background,
+                bg_fill: tokens.hovered_ui_element_background,
+                bg_stroke: Stroke::new(
+                    shape.hovered.bg_stroke_width,
+                    tokens.hovered_ui_element_border,
+                ), // e.g. hover over window edge or button
+                fg_stroke: Stroke::new(shape.hovered.fg_stroke_width, tokens.high_contrast_text),
+                rounding: Rounding::same(shape.hovered.rounding),
+                expansion: shape.hovered.expansion,
+            },
+            active: WidgetVisuals {
+                weak_bg_fill: tokens.active_ui_element_background,
+                bg_fill: tokens.active_ui_element_background,
+                bg_stroke: Stroke::new(
+                    shape.active.bg_stroke_width,
+                    tokens.ui_element_border_and_focus_rings,
+                ),
+                fg_stroke: Stroke::new(shape.active.fg_stroke_width, tokens.high_contrast_text),
+                rounding: Rounding::same(shape.active.rounding),
+                expansion: shape.active.expansion,
+            },
+            open: WidgetVisuals {
+                weak_bg_fill: tokens.active_ui_element_background,
+                bg_fill: tokens.active_ui_element_background,
+                bg_stroke: Stroke::new(
+                    shape.open.bg_stroke_width,
+                    tokens.ui_element_border_and_focus_rings,
+                ),
+                fg_stroke: Stroke::new(shape.open.fg_stroke_width, tokens.high_contrast_text),
+                rounding: Rounding::same(shape.open.rounding),
+                expansion: shape.open.expansion,
+            },
+        };
+
+        visuals.selection = selection;
+        visuals.widgets = widgets;
+        visuals.text_cursor = text_cursor;
+        visuals.extreme_bg_color = tokens.app_background; // e.g. TextEdit background
+        visuals.code_bg_color = tokens.ui_element_background;
+        visuals.window_fill = tokens.subtle_background;
+        visuals.window_stroke = Stroke::new(
+            shape.window_stroke_width,
+            tokens.subtle_borders_and_separators,
+        );
+        visuals.window_rounding = Rounding::same(shape.window_rounding);
+        visuals.menu_rounding = Rounding::same(shape.menu_rounding);
+        visuals.panel_fill = tokens.subtle_background;
+        visuals.hyperlink_color = tokens.hovered_solid_backgrounds;
+        // a shadow has to be darker than the background, so in dark mode
+        // only the opacity of the alpha token is used
+        let shadow_color = if visuals.dark_mode {
+            Color32::from_black_alpha(tokens.alpha[7].a())
+        } else {
+            tokens.alpha[3]
+        };
+        visuals.window_shadow = shape.window_shadow.shadow(shadow_color);
+        visuals.popup_shadow = shape.popup_shadow.shadow(shadow_color);
+        visuals.faint_bg_color = tokens.alpha[1]; // e.g. striped grid rows
+        visuals.error_fg_color = tokens.semantic.error[10];
+        visuals.warn_fg_color = tokens.semantic.warning[10];
+        //visuals.override_text_color = Some(tokens.text_color());
+    }
+}
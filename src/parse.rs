@@ -0,0 +1,251 @@
+use std::{fmt, str::FromStr};
+
+use crate::{tokens::ThemeColor, utils::PRESETS, Theme};
+
+/// The error returned when a theme or color can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseThemeError {
+    /// The name is not one of the preset `ThemeColor`s
+    UnknownColor(String),
+    /// A custom or hex color that is not a valid rgb value
+    InvalidColor(String),
+    /// A theme needs exactly 12 colors
+    WrongLength(usize),
+    /// A token of the theme is missing
+    MissingToken(String),
+    /// The name is not one of the 12 tokens
+    UnknownToken(String),
+    /// There is no theme with this name
+    UnknownTheme(String),
+    /// The text is not valid in the expected file format
+    InvalidFormat(String),
+}
+
+impl fmt::Display for ParseThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownColor(name) => write!(f, "unknown color: '{name}'"),
+            Self::InvalidColor(color) => write!(f, "invalid color: '{color}'"),
+            Self::WrongLength(len) => write!(f, "a theme needs 12 colors, found {len}"),
+            Self::MissingToken(token) => write!(f, "missing token: '{token}'"),
+            Self::UnknownToken(token) => write!(f, "unknown token: '{token}'"),
+            Self::UnknownTheme(name) => write!(f, "unknown theme: '{name}'"),
+            Self::InvalidFormat(err) => write!(f, "invalid format: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseThemeError {}
+
+impl FromStr for ThemeColor {
+    type Err = ParseThemeError;
+
+    /// Parses a preset name (`Gray`), a custom color (`Custom([23, 45, 77])`)
+    /// or a hex color (`#172d4d`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if s.starts_with('#') {
+            return parse_hex(&s).map(Self::Custom);
+        }
+        if let Some(inner) = s.strip_prefix("Custom(").and_then(|s| s.strip_suffix(')')) {
+            let invalid = || ParseThemeError::InvalidColor(s.clone());
+            let values = inner
+                .trim_end_matches(',')
+                .strip_prefix('[')
+                .and_then(|s| s.strip_suffix(']'))
+                .ok_or_else(invalid)?
+                .trim_end_matches(',')
+                .split(',')
+                .map(str::parse::<u8>)
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| invalid())?;
+            return <[u8; 3]>::try_from(values)
+                .map(Self::Custom)
+                .map_err(|_| invalid());
+        }
+        PRESETS
+            .iter()
+            .find(|preset| preset.label() == s)
+            .copied()
+            .ok_or(ParseThemeError::UnknownColor(s))
+    }
+}
+
+fn parse_hex(s: &str) -> Result<[u8; 3], ParseThemeError> {
+    let invalid = || ParseThemeError::InvalidColor(s.to_owned());
+    let hex = s.trim_start_matches('#');
+    // `from_str_radix` alone would also accept a sign, like in `+f+f+f`
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let mut rgb = [0; 3];
+    for (i, v) in rgb.iter_mut().enumerate() {
+        *v = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(rgb)
+}
+
+fn to_theme(colors: Vec<ThemeColor>) -> Result<Theme, ParseThemeError> {
+    let len = colors.len();
+    Theme::try_from(colors).map_err(|_| ParseThemeError::WrongLength(len))
+}
+
+/// Parses a theme from the text that "Copy theme to clipboard" produces,
+/// i.e. the `Debug` output of a [`Theme`].
+///
+/// # Errors
+///
+/// Returns a [`ParseThemeError`] if the text is not in brackets, a color is invalid or the theme doesn't have 12 colors.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{parse, tokens::ThemeColor};
+/// let mut theme = [ThemeColor::Gray; 12];
+/// theme[8] = ThemeColor::Custom([232, 210, 7]);
+/// let text = format!("{theme:#?}");
+/// assert_eq!(parse::theme_from_debug(&text), Ok(theme));
+/// assert!(matches!(
+///     parse::theme_from_debug("[Custom([1, 2, 3]]"),
+///     Err(parse::ParseThemeError::InvalidFormat(_))
+/// ));
+/// assert!("#+f+f+f".parse::<ThemeColor>().is_err());
+/// ```
+pub fn theme_from_debug(s: &str) -> Result<Theme, ParseThemeError> {
+    let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let inner = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| {
+            ParseThemeError::InvalidFormat("a theme is a list of colors in '[' and ']'".to_owned())
+        })?;
+    let unbalanced =
+        || ParseThemeError::InvalidFormat("unbalanced brackets or parentheses".to_owned());
+    // split on the commas between colors, not the ones inside `Custom([r, g, b])`
+    let mut colors = vec![];
+    let mut depth = 0_u32;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.checked_sub(1).ok_or_else(unbalanced)?,
+            ',' if depth == 0 => {
+                colors.push(inner[start..i].parse()?);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err(unbalanced());
+    }
+    if !inner[start..].is_empty() {
+        colors.push(inner[start..].parse()?);
+    }
+    to_theme(colors)
+}
+
+/// Parses a theme from a list of 12 hex colors, separated by commas or whitespace.
+/// All colors become [`ThemeColor::Custom`].
+///
+/// # Errors
+///
+/// Returns a [`ParseThemeError`] if a color is invalid or the list doesn't have 12 colors.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::parse;
+/// let text = "#8c958a #8c958a #8c958a #7aa6a8 #757575 #7aa6a8 #7aa6a8 #7aa6a8 #3b4761 #3b4761 #b9b2a8 #b9b2a8";
+/// assert!(parse::theme_from_hex(text).is_ok());
+/// ```
+pub fn theme_from_hex(s: &str) -> Result<Theme, ParseThemeError> {
+    let colors = s
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|v| !v.is_empty())
+        .map(|v| parse_hex(v).map(ThemeColor::Custom))
+        .collect::<Result<Vec<_>, _>>()?;
+    to_theme(colors)
+}
+
+/// Parses a theme from either the `Debug` output or a hex list.
+///
+/// # Errors
+///
+/// Returns a [`ParseThemeError`] if the text is neither a valid `Debug` theme nor a valid hex list.
+pub fn theme_from_str(s: &str) -> Result<Theme, ParseThemeError> {
+    if s.trim_start().starts_with('[') {
+        theme_from_debug(s)
+    } else {
+        theme_from_hex(s)
+    }
+}
+
+/// Parses a theme from a GIMP/Inkscape `.gpl` palette with 12 colors,
+/// which are mapped in order onto the 12 tokens. All colors become [`ThemeColor::Custom`].
+///
+/// # Errors
+///
+/// Returns a [`ParseThemeError`] if the text is not a `.gpl` palette, a color is invalid
+/// or the palette doesn't have 12 colors.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::parse;
+/// let mut gpl = String::from("GIMP Palette\nName: Yellow\n#\n");
+/// for _ in 0..12 {
+///     gpl.push_str("232 210   7\tyellow\n");
+/// }
+/// assert!(parse::theme_from_gpl(&gpl).is_ok());
+/// ```
+pub fn theme_from_gpl(s: &str) -> Result<Theme, ParseThemeError> {
+    let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+    if lines.next() != Some("GIMP Palette") {
+        return Err(ParseThemeError::InvalidFormat(
+            "missing 'GIMP Palette' header".to_owned(),
+        ));
+    }
+    let colors = lines
+        .filter(|line| {
+            !line.starts_with('#') && !line.starts_with("Name:") && !line.starts_with("Columns:")
+        })
+        .map(|line| {
+            let invalid = || ParseThemeError::InvalidColor(line.to_owned());
+            let values = line
+                .split_whitespace()
+                .take(3)
+                .map(str::parse::<u8>)
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| invalid())?;
+            <[u8; 3]>::try_from(values)
+                .map(ThemeColor::Custom)
+                .map_err(|_| invalid())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    to_theme(colors)
+}
+
+/// Parses a theme from the Design Tokens (DTCG) JSON that [`crate::export::dtcg`] produces.
+/// All colors become [`ThemeColor::Custom`].
+///
+/// # Errors
+///
+/// Returns a [`ParseThemeError`] if the JSON is invalid or one of the 12 colors of the `theme` group is missing.
+#[cfg(feature = "json")]
+pub fn theme_from_dtcg(s: &str) -> Result<Theme, ParseThemeError> {
+    let json: serde_json::Value =
+        serde_json::from_str(s).map_err(|err| ParseThemeError::InvalidFormat(err.to_string()))?;
+    let colors = (0..12)
+        .map(|i| {
+            let name = crate::export::token_name(i);
+            json["theme"][&name]["$value"]
+                .as_str()
+                .ok_or(ParseThemeError::MissingToken(name))
+                .and_then(parse_hex)
+                .map(ThemeColor::Custom)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    to_theme(colors)
+}
@@ -0,0 +1,111 @@
+use crate::{
+    utils::{THEMES, THEME_NAMES},
+    Theme,
+};
+
+/// The id of a theme in a [`ThemeRegistry`]. Ids are never reused, also not after a theme is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThemeId(u32);
+
+/// A theme with its name and id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedTheme {
+    pub id: ThemeId,
+    pub name: String,
+    pub theme: Theme,
+}
+
+/// The named themes that [`crate::Colorix::themes_dropdown`] offers.
+///
+/// The default registry holds the predefined themes of [`crate::utils`],
+/// with the same ids every time. Use [`ThemeRegistry::new`] for an empty one.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{registry::ThemeRegistry, tokens::ThemeColor};
+/// let mut registry = ThemeRegistry::default();
+/// let id = registry.add("Yellow", [ThemeColor::Custom([232, 210, 7]); 12]);
+/// assert_eq!(registry.find("Yellow").map(|t| t.id), Some(id));
+/// registry.remove(id);
+/// assert!(registry.get(id).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeRegistry {
+    themes: Vec<NamedTheme>,
+    next_id: u32,
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        for (name, theme) in THEME_NAMES.iter().zip(THEMES) {
+            registry.add(*name, theme);
+        }
+        registry
+    }
+}
+
+impl ThemeRegistry {
+    /// An empty registry, without the predefined themes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            themes: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Adds a theme at the end and returns its id.
+    /// If a theme with this name exists, its colors are replaced and it keeps its id.
+    pub fn add(&mut self, name: impl Into<String>, theme: Theme) -> ThemeId {
+        let name = name.into();
+        if let Some(named) = self.themes.iter_mut().find(|t| t.name == name) {
+            named.theme = theme;
+            return named.id;
+        }
+        let id = ThemeId(self.next_id);
+        self.next_id += 1;
+        self.themes.push(NamedTheme { id, name, theme });
+        id
+    }
+
+    /// Removes a theme and returns it, if it was registered.
+    pub fn remove(&mut self, id: ThemeId) -> Option<NamedTheme> {
+        let i = self.themes.iter().position(|t| t.id == id)?;
+        Some(self.themes.remove(i))
+    }
+
+    #[must_use]
+    pub fn get(&self, id: ThemeId) -> Option<&NamedTheme> {
+        self.themes.iter().find(|t| t.id == id)
+    }
+
+    /// The theme with this name.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<&NamedTheme> {
+        self.themes.iter().find(|t| t.name == name)
+    }
+
+    /// The id of the first registered theme with these colors.
+    #[must_use]
+    pub fn id_of(&self, theme: &Theme) -> Option<ThemeId> {
+        self.themes.iter().find(|t| &t.theme == theme).map(|t| t.id)
+    }
+
+    /// The themes in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &NamedTheme> {
+        self.themes.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.themes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.themes.is_empty()
+    }
+}
@@ -1,16 +1,126 @@
 use egui::{epaint::Hsva, Color32};
 use palette::{num::MulAdd, Darken, FromColor, IntoColor, Lighten, LinSrgb, Okhsl, OklabHue, Srgb};
 
-use crate::{apca::estimate_lc, tokens::ThemeColor};
+use crate::{tokens::ThemeColor, ContrastModel};
+
+/// The 12 steps of a color scale, computed with the same algorithm that is used for the ui.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{scales::Scale, tokens::ThemeColor};
+/// // the light and dark scales of a custom color, e.g. to color a chart
+/// let light = Scale::light(ThemeColor::Custom([232, 210, 7]));
+/// let dark = Scale::dark(ThemeColor::Custom([232, 210, 7]));
+/// let bar_fill = light.colors[8];
+/// assert_ne!(light.colors, dark.colors);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    pub colors: [Color32; 12],
+    pub okhsl: [Okhsl; 12],
+}
+
+impl Scale {
+    /// The scale of a color in light or dark mode.
+    #[must_use]
+    pub fn new(color: ThemeColor, dark_mode: bool) -> Self {
+        Self::with_model(color, dark_mode, ContrastModel::default())
+    }
+
+    /// The scale of a color in light or dark mode, adjusted with the given contrast model.
+    #[must_use]
+    pub fn with_model(color: ThemeColor, dark_mode: bool, contrast_model: ContrastModel) -> Self {
+        let mut scales = Scales {
+            dark_mode,
+            contrast_model,
+            ..Default::default()
+        };
+        scales.process_color(color);
+        Self {
+            colors: scales.scale,
+            okhsl: scales.okhsl,
+        }
+    }
+
+    /// The translucent version of this scale, that looks the same over `background`.
+    #[must_use]
+    pub fn alpha(&self, background: Color32) -> [Color32; 12] {
+        alpha_scale(&self.colors, background)
+    }
+
+    /// The scale of a color in light mode.
+    #[must_use]
+    pub fn light(color: ThemeColor) -> Self {
+        Self::new(color, false)
+    }
+
+    /// The scale of a color in dark mode.
+    #[must_use]
+    pub fn dark(color: ThemeColor) -> Self {
+        Self::new(color, true)
+    }
+}
+
+/// The most translucent color that looks like `color` when painted over `background`.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{scales::{alpha_color, Scale}, tokens::ThemeColor};
+/// let scale = Scale::light(ThemeColor::Indigo).colors;
+/// let background = scale[0];
+/// for color in scale {
+///     let [r, g, b, a] = alpha_color(color, background).to_srgba_unmultiplied();
+///     let a = f32::from(a) / 255.;
+///     // blending the alpha color over the background gives the original color back
+///     for (fg, (bg, expected)) in [r, g, b].into_iter().zip(background.to_array().into_iter().zip(color.to_array())) {
+///         let blended = f32::from(fg) * a + f32::from(bg) * (1. - a);
+///         assert!((blended - f32::from(expected)).abs() <= 1., "{color:?}");
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn alpha_color(color: Color32, background: Color32) -> Color32 {
+    let [r, g, b, _] = color.to_array().map(f32::from);
+    let [bg_r, bg_g, bg_b, _] = background.to_array().map(f32::from);
+    let (fg, bg) = ([r, g, b], [bg_r, bg_g, bg_b]);
+    // per channel: the alpha needed when the foreground is pushed to black or white
+    let alpha = (0..3)
+        .map(|i| match fg[i] - bg[i] {
+            d if d > 0. => d / (255. - bg[i]),
+            d if d < 0. => -d / bg[i],
+            _ => 0.,
+        })
+        .fold(0., f32::max);
+    if alpha <= 0. {
+        return Color32::TRANSPARENT;
+    }
+    // round up to a representable alpha, so the foreground channels stay within 0..=255
+    let alpha = (alpha * 255.).ceil() / 255.;
+    let [r, g, b] = [0, 1, 2].map(|i| {
+        ((fg[i] - bg[i]).mul_add(1. / alpha, bg[i]))
+            .round()
+            .clamp(0., 255.) as u8
+    });
+    Color32::from_rgba_unmultiplied(r, g, b, (alpha * 255.) as u8)
+}
+
+/// The alpha version of every step of a scale.
+#[must_use]
+pub fn alpha_scale(scale: &[Color32; 12], background: Color32) -> [Color32; 12] {
+    scale.map(|color| alpha_color(color, background))
+}
 
 #[derive(Debug, Default, Clone)]
-pub struct Scales {
+pub(crate) struct Scales {
     pub custom: Hsva,
     pub okhsl: [Okhsl; 12],
     pub rgbs: [LinSrgb; 12],
     pub srgb: LinSrgb,
     pub scale: [Color32; 12],
     pub dark_mode: bool,
+    pub contrast_model: ContrastModel,
 }
 
 impl Scales {
@@ -106,8 +216,10 @@ impl Scales {
         self.okhsl[11].lightness *= 0.9;
 
         let (r, g, b) = Srgb::from_linear(hsl.into_color()).into();
-        let lc = estimate_lc(Color32::WHITE, Color32::from_rgb(r, g, b));
-        if lc > -46. {
+        let contrast = self
+            .contrast_model
+            .contrast_of_white(Color32::from_rgb(r, g, b));
+        if contrast < self.contrast_model.light_scale_threshold() {
             self.okhsl[8].lightness = 0.68;
             self.okhsl[9].lightness = self.okhsl[8].lightness * 0.9;
             self.okhsl[9].saturation = self.okhsl[8].saturation * 0.9;
@@ -175,8 +287,10 @@ impl Scales {
             self.okhsl[10].saturation = self.okhsl[10].saturation.clamp(0.0, hsl.saturation * 0.9);
         }
         let (r, g, b) = Srgb::from_linear(self.srgb.into_color()).into();
-        let lc = estimate_lc(Color32::WHITE, Color32::from_rgb(r, g, b));
-        if lc < -95.4 {
+        let contrast = self
+            .contrast_model
+            .contrast_of_white(Color32::from_rgb(r, g, b));
+        if contrast > self.contrast_model.dark_scale_threshold() {
             self.okhsl[8] = hsl.lighten(0.3);
             self.okhsl[8].saturation = hsl.saturation * 1.25;
             self.okhsl[9] = self.okhsl[9].lighten(0.25);
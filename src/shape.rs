@@ -0,0 +1,178 @@
+use egui::Vec2;
+
+/// Rounding, stroke widths and expansion of a widget in one interaction state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WidgetShape {
+    pub rounding: f32,
+    /// The width of the border
+    pub bg_stroke_width: f32,
+    /// The width of text and icons
+    pub fg_stroke_width: f32,
+    /// How much the widget grows, e.g. when hovered
+    pub expansion: f32,
+}
+
+impl WidgetShape {
+    const fn new(
+        rounding: f32,
+        bg_stroke_width: f32,
+        fg_stroke_width: f32,
+        expansion: f32,
+    ) -> Self {
+        Self {
+            rounding,
+            bg_stroke_width,
+            fg_stroke_width,
+            expansion,
+        }
+    }
+}
+
+/// The geometry of a shadow. The color comes from the alpha tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShadowShape {
+    pub offset: Vec2,
+    pub blur: f32,
+    pub spread: f32,
+}
+
+impl ShadowShape {
+    const fn new(x: f32, y: f32, blur: f32, spread: f32) -> Self {
+        Self {
+            offset: Vec2::new(x, y),
+            blur,
+            spread,
+        }
+    }
+
+    pub(crate) const fn shadow(self, color: egui::Color32) -> egui::Shadow {
+        egui::Shadow {
+            offset: self.offset,
+            blur: self.blur,
+            spread: self.spread,
+            color,
+        }
+    }
+}
+
+/// Everything besides color that defines the look of a theme.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::{shape::StyleShape, utils, Colorix};
+/// let ctx = egui::Context::default();
+/// let mut colorix = Colorix::init(&ctx, utils::EGUI_THEME);
+/// colorix.set_shape(&ctx, StyleShape::SHARP);
+/// let visuals = ctx.style().visuals.clone();
+/// assert_eq!(visuals.widgets.inactive.rounding, egui::Rounding::ZERO);
+/// assert_eq!(visuals.widgets.active.bg_stroke.width, 2.0);
+/// assert_eq!(visuals.window_shadow.offset, egui::vec2(4.0, 4.0));
+///
+/// // the default keeps the widget shapes Colorix always used, and egui's windows and shadows
+/// colorix.set_shape(&ctx, StyleShape::DEFAULT);
+/// let visuals = ctx.style().visuals.clone();
+/// let widgets = &visuals.widgets;
+/// for (widget, rounding, fg_stroke_width, expansion) in [
+///     (&widgets.noninteractive, 2.0, 1.0, 0.0),
+///     (&widgets.inactive, 2.0, 1.0, 0.0),
+///     (&widgets.hovered, 3.0, 1.5, 1.0),
+///     (&widgets.active, 2.0, 2.0, 1.0),
+///     (&widgets.open, 2.0, 1.0, 0.0),
+/// ] {
+///     assert_eq!(widget.rounding, egui::Rounding::same(rounding));
+///     assert_eq!(widget.bg_stroke.width, 1.0);
+///     assert_eq!(widget.fg_stroke.width, fg_stroke_width);
+///     assert_eq!(widget.expansion, expansion);
+/// }
+/// let egui_visuals = egui::Visuals::dark();
+/// assert_eq!(visuals.window_rounding, egui_visuals.window_rounding);
+/// assert_eq!(visuals.menu_rounding, egui_visuals.menu_rounding);
+/// assert_eq!(visuals.window_stroke.width, egui_visuals.window_stroke.width);
+/// for (shadow, egui_shadow) in [
+///     (visuals.window_shadow, egui_visuals.window_shadow),
+///     (visuals.popup_shadow, egui_visuals.popup_shadow),
+/// ] {
+///     assert_eq!((shadow.offset, shadow.blur, shadow.spread), (egui_shadow.offset, egui_shadow.blur, egui_shadow.spread));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StyleShape {
+    pub noninteractive: WidgetShape,
+    pub inactive: WidgetShape,
+    pub hovered: WidgetShape,
+    pub active: WidgetShape,
+    pub open: WidgetShape,
+    pub window_rounding: f32,
+    pub menu_rounding: f32,
+    pub window_stroke_width: f32,
+    pub window_shadow: ShadowShape,
+    pub popup_shadow: ShadowShape,
+}
+
+impl StyleShape {
+    /// Slightly rounded widgets, close to the egui defaults.
+    pub const DEFAULT: Self = Self {
+        noninteractive: WidgetShape::new(2.0, 1.0, 1.0, 0.0),
+        inactive: WidgetShape::new(2.0, 1.0, 1.0, 0.0),
+        hovered: WidgetShape::new(3.0, 1.0, 1.5, 1.0),
+        active: WidgetShape::new(2.0, 1.0, 2.0, 1.0),
+        open: WidgetShape::new(2.0, 1.0, 1.0, 0.0),
+        window_rounding: 6.0,
+        menu_rounding: 6.0,
+        window_stroke_width: 1.0,
+        window_shadow: ShadowShape::new(10.0, 20.0, 15.0, 0.0),
+        popup_shadow: ShadowShape::new(6.0, 10.0, 8.0, 0.0),
+    };
+
+    /// No rounding and short, hard shadows.
+    pub const SHARP: Self = Self {
+        noninteractive: WidgetShape::new(0.0, 1.0, 1.0, 0.0),
+        inactive: WidgetShape::new(0.0, 1.0, 1.0, 0.0),
+        hovered: WidgetShape::new(0.0, 1.0, 1.5, 0.0),
+        active: WidgetShape::new(0.0, 2.0, 2.0, 0.0),
+        open: WidgetShape::new(0.0, 1.0, 1.0, 0.0),
+        window_rounding: 0.0,
+        menu_rounding: 0.0,
+        window_stroke_width: 1.0,
+        window_shadow: ShadowShape::new(4.0, 4.0, 0.0, 0.0),
+        popup_shadow: ShadowShape::new(2.0, 2.0, 0.0, 0.0),
+    };
+
+    /// Generous rounding and wide, diffuse shadows.
+    pub const SOFT: Self = Self {
+        noninteractive: WidgetShape::new(6.0, 1.0, 1.0, 0.0),
+        inactive: WidgetShape::new(6.0, 1.0, 1.0, 0.0),
+        hovered: WidgetShape::new(6.0, 1.0, 1.5, 1.0),
+        active: WidgetShape::new(6.0, 1.0, 2.0, 1.0),
+        open: WidgetShape::new(6.0, 1.0, 1.0, 0.0),
+        window_rounding: 12.0,
+        menu_rounding: 8.0,
+        window_stroke_width: 0.0,
+        window_shadow: ShadowShape::new(0.0, 12.0, 32.0, 2.0),
+        popup_shadow: ShadowShape::new(0.0, 6.0, 16.0, 1.0),
+    };
+
+    /// Fully rounded widgets, like pills.
+    pub const PILL: Self = Self {
+        noninteractive: WidgetShape::new(4.0, 1.0, 1.0, 0.0),
+        inactive: WidgetShape::new(100.0, 1.0, 1.0, 0.0),
+        hovered: WidgetShape::new(100.0, 1.0, 1.5, 1.0),
+        active: WidgetShape::new(100.0, 1.0, 2.0, 1.0),
+        open: WidgetShape::new(100.0, 1.0, 1.0, 0.0),
+        window_rounding: 16.0,
+        menu_rounding: 12.0,
+        window_stroke_width: 1.0,
+        window_shadow: ShadowShape::new(0.0, 12.0, 24.0, 0.0),
+        popup_shadow: ShadowShape::new(0.0, 6.0, 12.0, 0.0),
+    };
+}
+
+impl Default for StyleShape {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -1,13 +1,19 @@
-use crate::apca::estimate_lc;
-use egui::{
-    self,
-    style::{TextCursorStyle, WidgetVisuals},
-    Color32, Rounding, Stroke,
+use crate::{
+    apca::{background_color_for, closest_lightness, estimate_lc, text_color_for},
+    audit::ContrastLevel,
+    cvd::ColorBlindness,
+    mapping::VisualsMapping,
+    scales::{alpha_scale, Scale, Scales},
+    shape::StyleShape,
+    wcag::{self, contrast_ratio, AA_TEXT},
+    ContrastModel, Theme,
 };
-use palette::{LinSrgb, Srgb};
+use egui::{self, Color32};
+use palette::{FromColor, LinSrgb, Mix, Oklab, Srgb};
 
 /// The functional UI elements mapped to a scale
 #[derive(Default, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorTokens {
     pub app_background: Color32,
     pub subtle_background: Color32,
@@ -23,19 +29,181 @@ pub struct ColorTokens {
     pub high_contrast_text: Color32,
     pub inverse_color: bool,
     pub on_accent: Color32,
+    /// The translucent version of every token, that looks the same over `app_background`
+    pub alpha: [Color32; 12],
+    /// The translucent version of the whole scale of the color of every token,
+    /// e.g. `alpha_scales[8]` for the accent color. `alpha_scales[i][i]` is `alpha[i]`.
+    ///
+    /// ```
+    /// use egui_colors::{scales::Scale, utils, Colorix};
+    /// let colorix = Colorix::init(&egui::Context::default(), utils::INDIGO_JADE);
+    /// let tokens = colorix.tokens_of(egui::Theme::Light);
+    /// let accent = Scale::light(utils::INDIGO_JADE[8]).alpha(tokens.app_background);
+    /// assert_eq!(tokens.alpha_scales[8], accent);
+    /// assert_eq!(tokens.alpha_scales[8][8], tokens.alpha[8]);
+    /// ```
+    pub alpha_scales: [[Color32; 12]; 12],
+    pub semantic: SemanticTokens,
+}
+
+fn lerp_oklab(from: Color32, to: Color32, t: f32) -> Color32 {
+    let to_oklab = |color: Color32| {
+        let [r, g, b, _] = color.to_srgba_unmultiplied();
+        Oklab::from_color(Srgb::new(r, g, b).into_linear::<f32>())
+    };
+    let mixed = to_oklab(from).mix(to_oklab(to), t);
+    let (r, g, b): (u8, u8, u8) = Srgb::from_linear(LinSrgb::from_color(mixed)).into();
+    let alpha = f32::from(from.a()).mul_add(1. - t, f32::from(to.a()) * t);
+    Color32::from_rgba_unmultiplied(r, g, b, alpha.round() as u8)
+}
+
+/// The colors of the semantic roles, used for status messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SemanticColors {
+    pub error: ThemeColor,
+    pub warning: ThemeColor,
+    pub success: ThemeColor,
+    pub info: ThemeColor,
+}
+
+impl Default for SemanticColors {
+    fn default() -> Self {
+        Self {
+            error: ThemeColor::Red,
+            warning: ThemeColor::Orange,
+            success: ThemeColor::Green,
+            info: ThemeColor::Blue,
+        }
+    }
+}
+
+/// The 12 step scales of the semantic roles.
+///
+/// As with the other tokens, step 3 (index 2) is meant for backgrounds, step 7 (index 6) for borders,
+/// step 9 (index 8) for solid fills and step 11 (index 10) for text.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SemanticTokens {
+    pub error: [Color32; 12],
+    pub warning: [Color32; 12],
+    pub success: [Color32; 12],
+    pub info: [Color32; 12],
+}
+
+impl SemanticTokens {
+    pub(crate) fn new(
+        colors: &SemanticColors,
+        dark_mode: bool,
+        contrast_model: ContrastModel,
+    ) -> Self {
+        let scale = |color| Scale::with_model(color, dark_mode, contrast_model).colors;
+        Self {
+            error: scale(colors.error),
+            warning: scale(colors.warning),
+            success: scale(colors.success),
+            info: scale(colors.info),
+        }
+    }
+
+    /// The translucent version of the four scales, that looks the same over `background`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{utils, Colorix};
+    /// let colorix = Colorix::init(&egui::Context::default(), utils::EGUI_THEME);
+    /// let tokens = colorix.tokens;
+    /// let alpha = tokens.semantic.alpha(tokens.app_background);
+    /// assert!(alpha.error[8].a() < 255);
+    /// assert_eq!(alpha.error[8], egui_colors::scales::alpha_color(tokens.semantic.error[8], tokens.app_background));
+    /// ```
+    #[must_use]
+    pub fn alpha(&self, background: Color32) -> Self {
+        Self {
+            error: alpha_scale(&self.error, background),
+            warning: alpha_scale(&self.warning, background),
+            success: alpha_scale(&self.success, background),
+            info: alpha_scale(&self.info, background),
+        }
+    }
 }
 
 impl ColorTokens {
-    pub(crate) fn color_on_accent(&mut self) {
-        let lc = estimate_lc(egui::Color32::WHITE, self.solid_backgrounds);
-        if lc > -46. {
-            self.inverse_color = true;
-            let mut hsva: egui::ecolor::Hsva = self.solid_backgrounds.into();
-            hsva.s = 0.7;
-            hsva.v = 0.01;
-            self.on_accent = hsva.into();
+    /// Computes the tokens of a theme for either light or dark mode.
+    pub(crate) fn from_theme(
+        theme: &Theme,
+        dark_mode: bool,
+        contrast_model: ContrastModel,
+    ) -> Self {
+        let mut scales = Scales {
+            dark_mode,
+            contrast_model,
+            ..Default::default()
+        };
+        let mut tokens = Self::default();
+        let solid_scales = tokens.process_theme(theme, &mut scales);
+        tokens.color_on_accent(contrast_model);
+        tokens.update_alpha();
+        tokens.alpha_scales = solid_scales.map(|scale| alpha_scale(&scale, tokens.app_background));
+        tokens
+    }
+
+    pub(crate) fn update_alpha(&mut self) {
+        let solid = std::array::from_fn(|i| self.get_token(i));
+        self.alpha = alpha_scale(&solid, self.app_background);
+    }
+
+    /// Sets the tokens and returns the scale of the color of every token.
+    fn process_theme(&mut self, theme: &Theme, scales: &mut Scales) -> [[Color32; 12]; 12] {
+        let mut solid_scales = [[Color32::default(); 12]; 12];
+        let mut processed: Vec<usize> = vec![];
+        for (i, v) in theme.iter().enumerate() {
+            if !processed.contains(&i) {
+                scales.process_color(*v);
+                self.update_schema(i, scales.scale[i]);
+                solid_scales[i] = scales.scale;
+                if i < theme.len() {
+                    for (j, w) in theme[i + 1..].iter().enumerate() {
+                        if w == v {
+                            self.update_schema(j + i + 1, scales.scale[j + i + 1]);
+                            solid_scales[j + i + 1] = scales.scale;
+                            processed.push(j + i + 1);
+                        }
+                    }
+                }
+            }
+        }
+        solid_scales
+    }
+
+    pub(crate) fn color_on_accent(&mut self, contrast_model: ContrastModel) {
+        let mut hsva: egui::ecolor::Hsva = self.solid_backgrounds.into();
+        hsva.s = 0.7;
+        hsva.v = 0.01;
+        let dark: Color32 = hsva.into();
+        self.inverse_color = match contrast_model {
+            ContrastModel::Apca => {
+                contrast_model.contrast_of_white(self.solid_backgrounds)
+                    < contrast_model.on_accent_threshold()
+            }
+            // the text color with the higher ratio, solved below if neither reaches AA
+            ContrastModel::Wcag => {
+                contrast_ratio(dark, self.solid_backgrounds)
+                    > contrast_ratio(Color32::WHITE, self.solid_backgrounds)
+            }
+        };
+        self.on_accent = if self.inverse_color {
+            dark
         } else {
-            self.on_accent = egui::Color32::WHITE;
+            Color32::WHITE
+        };
+        if contrast_model == ContrastModel::Wcag
+            && contrast_ratio(self.on_accent, self.solid_backgrounds) < AA_TEXT
+        {
+            self.inverse_color = true;
+            self.on_accent = wcag::text_color_for(dark, self.solid_backgrounds, AA_TEXT)
+                .unwrap_or(Color32::BLACK);
         }
     }
 
@@ -74,76 +242,149 @@ impl ColorTokens {
         }
     }
 
-    pub(crate) fn set_egui_visuals(&self, ctx: &egui::Context) {
-        if ctx.style().visuals.dark_mode {
-            ctx.set_visuals_of(egui::Theme::Dark, egui::Visuals::dark());
+    /// A high contrast variant of these tokens: text pushed to (almost) maximum Lc,
+    /// stronger borders and the subtle background flattened into the app background.
+    /// Accents are pushed so that hyperlinks stand out and text on accents is white in light mode
+    /// and black in dark mode. Borders, focus rings and accents get the non-text contrast
+    /// of `contrast_model` on the backgrounds they are drawn on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_colors::{registry::ThemeRegistry, Colorix, ContrastModel};
+    /// let ctx = egui::Context::default();
+    /// for named in ThemeRegistry::default().iter() {
+    ///     for model in [ContrastModel::Apca, ContrastModel::Wcag] {
+    ///         for mode in [egui::Theme::Light, egui::Theme::Dark] {
+    ///             ctx.set_theme(mode);
+    ///             let mut colorix = Colorix::init(&ctx, named.theme);
+    ///             colorix.set_contrast_model(&ctx, model);
+    ///             colorix.set_high_contrast(&ctx, true);
+    ///             let report = colorix.contrast_report();
+    ///             assert!(report.passes(), "{} with {model:?} in {mode:?}", named.name);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn high_contrast(&self, dark_mode: bool, contrast_model: ContrastModel) -> Self {
+        let mut tokens = Self {
+            subtle_background: self.app_background,
+            subtle_borders_and_separators: self.ui_element_border_and_focus_rings,
+            ui_element_border_and_focus_rings: self.hovered_ui_element_border,
+            hovered_ui_element_border: self.solid_backgrounds,
+            ..*self
+        };
+        // positive Lc is dark text on a light background
+        let (sign, extreme) = if dark_mode {
+            (-1., Color32::WHITE)
         } else {
-            ctx.set_visuals_of(egui::Theme::Light, egui::Visuals::light());
-        }
-        let selection = egui::style::Selection {
-            bg_fill: self.solid_backgrounds,
-            stroke: Stroke::new(1.0, self.on_accent),
+            (1., Color32::BLACK)
         };
-        let text_cursor = TextCursorStyle {
-            stroke: Stroke::new(2.0, self.low_contrast_text),
-            ..Default::default()
+        let push_text = |text, lc: f32| {
+            text_color_for(text, tokens.app_background, sign * lc).unwrap_or(extreme)
         };
-        let widgets = egui::style::Widgets {
-            noninteractive: WidgetVisuals {
-                weak_bg_fill: self.subtle_background,
-                bg_fill: self.subtle_background,
-                bg_stroke: Stroke::new(1.0, self.subtle_borders_and_separators), // separators, indentation lines
-                fg_stroke: Stroke::new(1.0, self.low_contrast_text), // normal text color
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
-            },
-            inactive: WidgetVisuals {
-                weak_bg_fill: self.ui_element_background, // button background
-                bg_fill: self.ui_element_background,      // checkbox background
-                bg_stroke: Stroke::new(1.0, self.ui_element_background),
-                fg_stroke: Stroke::new(1.0, self.low_contrast_text), // button text
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
-            },
-            hovered: WidgetVisuals {
-                weak_bg_fill: self.hovered_ui_element_background,
-                bg_fill: self.hovered_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.hovered_ui_element_border), // e.g. hover over window edge or button
-                fg_stroke: Stroke::new(1.5, self.high_contrast_text),
-                rounding: Rounding::same(3.0),
-                expansion: 1.0,
-            },
-            active: WidgetVisuals {
-                weak_bg_fill: self.active_ui_element_background,
-                bg_fill: self.active_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.ui_element_border_and_focus_rings),
-                fg_stroke: Stroke::new(2.0, self.high_contrast_text),
-                rounding: Rounding::same(2.0),
-                expansion: 1.0,
+        tokens.low_contrast_text = push_text(self.low_contrast_text, 90.);
+        tokens.high_contrast_text = push_text(self.high_contrast_text, 100.);
+        // hyperlinks use the hovered accent as text color
+        tokens.hovered_solid_backgrounds = push_text(self.hovered_solid_backgrounds, 70.);
+        // accents get darker in light mode and lighter in dark mode, so text on them is light or dark
+        tokens.inverse_color = dark_mode;
+        tokens.on_accent = if dark_mode {
+            Color32::BLACK
+        } else {
+            Color32::WHITE
+        };
+        let push_accent =
+            |accent| background_color_for(tokens.on_accent, accent, -sign * 80.).unwrap_or(accent);
+        tokens.solid_backgrounds = push_accent(self.solid_backgrounds);
+        tokens.hovered_solid_backgrounds = push_accent(tokens.hovered_solid_backgrounds);
+        let visible_on = |color: Color32, backgrounds: &[Color32]| {
+            closest_lightness(color, !dark_mode, |color| {
+                backgrounds.iter().all(|&bg| {
+                    let (lc, ratio) = (estimate_lc(color, bg), contrast_ratio(color, bg));
+                    ContrastLevel::NonText.is_met(contrast_model, lc, ratio)
+                })
+            })
+            .unwrap_or(extreme)
+        };
+        tokens.ui_element_border_and_focus_rings = visible_on(
+            tokens.ui_element_border_and_focus_rings,
+            &[tokens.app_background, tokens.ui_element_background],
+        );
+        tokens.hovered_ui_element_border = visible_on(
+            tokens.hovered_ui_element_border,
+            &[tokens.hovered_ui_element_background],
+        );
+        tokens.solid_backgrounds = visible_on(tokens.solid_backgrounds, &[tokens.app_background]);
+        tokens.update_alpha();
+        tokens
+    }
+
+    /// How all tokens look with a color vision deficiency, e.g. to check
+    /// that focus rings, accents and status colors stay distinguishable.
+    #[must_use]
+    pub fn simulate(&self, color_blindness: ColorBlindness) -> Self {
+        let simulate_12 = |colors: &[Color32; 12]| colors.map(|c| color_blindness.simulate(c));
+        let mut tokens = Self {
+            on_accent: color_blindness.simulate(self.on_accent),
+            alpha: simulate_12(&self.alpha),
+            alpha_scales: self.alpha_scales.map(|scale| simulate_12(&scale)),
+            semantic: SemanticTokens {
+                error: simulate_12(&self.semantic.error),
+                warning: simulate_12(&self.semantic.warning),
+                success: simulate_12(&self.semantic.success),
+                info: simulate_12(&self.semantic.info),
             },
-            open: WidgetVisuals {
-                weak_bg_fill: self.active_ui_element_background,
-                bg_fill: self.active_ui_element_background,
-                bg_stroke: Stroke::new(1.0, self.ui_element_border_and_focus_rings),
-                fg_stroke: Stroke::new(1.0, self.high_contrast_text),
-                rounding: Rounding::same(2.0),
-                expansion: 0.0,
+            ..*self
+        };
+        for i in 0..12 {
+            tokens.update_schema(i, color_blindness.simulate(self.get_token(i)));
+        }
+        tokens
+    }
+
+    /// Interpolates every token in Oklab, `t` = 0 gives `self` and `t` = 1 gives `other`.
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let lerp_12 = |a: &[Color32; 12], b: &[Color32; 12]| {
+            std::array::from_fn(|i| lerp_oklab(a[i], b[i], t))
+        };
+        let mut tokens = Self {
+            inverse_color: other.inverse_color,
+            on_accent: lerp_oklab(self.on_accent, other.on_accent, t),
+            alpha: lerp_12(&self.alpha, &other.alpha),
+            alpha_scales: std::array::from_fn(|i| {
+                lerp_12(&self.alpha_scales[i], &other.alpha_scales[i])
+            }),
+            semantic: SemanticTokens {
+                error: lerp_12(&self.semantic.error, &other.semantic.error),
+                warning: lerp_12(&self.semantic.warning, &other.semantic.warning),
+                success: lerp_12(&self.semantic.success, &other.semantic.success),
+                info: lerp_12(&self.semantic.info, &other.semantic.info),
             },
+            ..Default::default()
         };
+        for i in 0..12 {
+            tokens.update_schema(i, lerp_oklab(self.get_token(i), other.get_token(i), t));
+        }
+        tokens
+    }
 
-        ctx.style_mut(|style| {
-            style.visuals.selection = selection;
-            style.visuals.widgets = widgets;
-            style.visuals.text_cursor = text_cursor;
-            style.visuals.extreme_bg_color = self.app_background; // e.g. TextEdit background
-            style.visuals.faint_bg_color = self.app_background; // striped grid is originally from_additive_luminance(5)
-            style.visuals.code_bg_color = self.ui_element_background;
-            style.visuals.window_fill = self.subtle_background;
-            style.visuals.window_stroke = Stroke::new(1.0, self.subtle_borders_and_separators);
-            style.visuals.panel_fill = self.subtle_background;
-            style.visuals.hyperlink_color = self.hovered_solid_backgrounds;
-            //style.visuals.override_text_color = Some(self.text_color());
-        });
+    /// The egui visuals of these tokens, based on the default light or dark visuals.
+    pub(crate) fn visuals(
+        &self,
+        shape: &StyleShape,
+        mapping: &dyn VisualsMapping,
+        dark_mode: bool,
+    ) -> egui::Visuals {
+        let mut visuals = if dark_mode {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        mapping.apply(self, shape, &mut visuals);
+        visuals
     }
 }
 
@@ -156,6 +397,7 @@ impl ColorTokens {
 /// my_theme[11] = ThemeColor::Custom([23, 45, 77]);
 /// ```
 #[derive(Default, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ThemeColor {
     #[default]
     Gray,
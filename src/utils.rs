@@ -115,6 +115,31 @@ pub(crate) const THEMES: [Theme; 7] = [
     OFFICE_GRAY,
 ];
 
+pub(crate) const PRESETS: [ThemeColor; 22] = [
+    ThemeColor::Gray,
+    ThemeColor::EguiBlue,
+    ThemeColor::Tomato,
+    ThemeColor::Red,
+    ThemeColor::Ruby,
+    ThemeColor::Crimson,
+    ThemeColor::Pink,
+    ThemeColor::Plum,
+    ThemeColor::Purple,
+    ThemeColor::Violet,
+    ThemeColor::Iris,
+    ThemeColor::Indigo,
+    ThemeColor::Blue,
+    ThemeColor::Cyan,
+    ThemeColor::Teal,
+    ThemeColor::Jade,
+    ThemeColor::Green,
+    ThemeColor::Grass,
+    ThemeColor::Brown,
+    ThemeColor::Bronze,
+    ThemeColor::Gold,
+    ThemeColor::Orange,
+];
+
 pub(crate) const LABELS: [&str; 12] = [
     "app background",
     "subtle background",
@@ -0,0 +1,67 @@
+use egui::Color32;
+
+use crate::apca::closest_lightness;
+
+/// The minimum ratio for normal text at level AA.
+pub const AA_TEXT: f32 = 4.5;
+/// The minimum ratio for large text and non-text elements at level AA.
+pub const AA_LARGE_TEXT: f32 = 3.0;
+/// The minimum ratio for normal text at level AAA.
+pub const AAA_TEXT: f32 = 7.0;
+
+fn linearize(channel: u8) -> f32 {
+    let c = f32::from(channel) / 255.0;
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// The relative luminance of a color as defined by WCAG 2.x, from 0 (black) to 1 (white).
+#[must_use]
+pub fn relative_luminance(color: Color32) -> f32 {
+    0.0722f32.mul_add(
+        linearize(color.b()),
+        0.2126f32.mul_add(linearize(color.r()), 0.7152 * linearize(color.g())),
+    )
+}
+
+/// The WCAG 2.x contrast ratio of two colors, from 1 to 21. The order of the colors does not matter.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::wcag::{contrast_ratio, AA_TEXT};
+/// use egui::Color32;
+/// assert!((contrast_ratio(Color32::BLACK, Color32::WHITE) - 21.).abs() < 0.01);
+/// assert!(contrast_ratio(Color32::GRAY, Color32::WHITE) < AA_TEXT);
+/// ```
+#[must_use]
+pub fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let (l_a, l_b) = (relative_luminance(a), relative_luminance(b));
+    (l_a.max(l_b) + 0.05) / (l_a.min(l_b) + 0.05)
+}
+
+/// The color closest to `text`, only changing its Okhsl lightness, that has at least `target_ratio` on `background`.
+///
+/// Text that is darker than the background is searched darker, lighter text lighter.
+/// `text` is returned unchanged if it already has enough contrast,
+/// and `None` if even black or white does not reach the target.
+///
+/// # Examples
+///
+/// ```
+/// use egui_colors::wcag::{contrast_ratio, text_color_for, AA_TEXT};
+/// use egui::Color32;
+/// let bg = Color32::from_rgb(102, 122, 167);
+/// let text = text_color_for(Color32::from_rgb(10, 15, 25), bg, AA_TEXT).unwrap();
+/// assert!(contrast_ratio(text, bg) >= AA_TEXT);
+/// ```
+#[must_use]
+pub fn text_color_for(text: Color32, background: Color32, target_ratio: f32) -> Option<Color32> {
+    let darker = relative_luminance(text) <= relative_luminance(background);
+    closest_lightness(text, darker, |color| {
+        contrast_ratio(color, background) >= target_ratio
+    })
+}
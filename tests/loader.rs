@@ -0,0 +1,201 @@
+#![cfg(feature = "loader")]
+
+use std::{
+    error::Error,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use egui_colors::{
+    loader::{LoadThemeError, ThemeLoader},
+    registry::ThemeRegistry,
+    tokens::ThemeColor,
+    utils,
+};
+
+type TestResult = Result<(), Box<dyn Error>>;
+
+const YELLOW: &str = r##"
+name = "Yellow"
+colors = ["Gray", "Gray", "Gray", "Gray", "Gray", "Gray",
+          "Gray", "Gray", "#e8d207", "#e8d207", "Gray", "Gray"]
+"##;
+
+/// An empty directory of its own for every test.
+fn theme_dir(test: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let dir = std::env::temp_dir().join(format!("egui_colors-{}-{test}", std::process::id()));
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Writes a file with a given modification time, so a change is seen even within the timer resolution.
+fn write(path: &Path, text: &str, modified: SystemTime) -> TestResult {
+    let mut file = File::create(path)?;
+    file.write_all(text.as_bytes())?;
+    file.set_modified(modified)?;
+    Ok(())
+}
+
+/// A modification time long ago, so a failed file is not read again until it changes.
+fn at(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+#[test]
+fn add_modify_rename_and_delete() -> TestResult {
+    let dir = theme_dir("lifecycle")?;
+    let path = dir.join("yellow.toml");
+    let mut registry = ThemeRegistry::default();
+    let mut loader = ThemeLoader::new(&dir);
+
+    write(&path, YELLOW, at(1))?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    let id = registry.find("Yellow").ok_or("not added")?.id;
+    assert_eq!(registry.len(), 8);
+
+    write(&path, &YELLOW.replace("#e8d207", "Crimson"), at(2))?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    let yellow = registry.find("Yellow").ok_or("removed on change")?;
+    assert_eq!(yellow.id, id);
+    assert_eq!(yellow.theme[8], ThemeColor::Crimson);
+
+    write(&path, &YELLOW.replace("Yellow", "Gold"), at(3))?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Yellow").is_none());
+    assert!(registry.find("Gold").is_some());
+    assert_eq!(registry.len(), 8);
+
+    std::fs::remove_file(&path)?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Gold").is_none());
+    assert!(registry.iter().eq(ThemeRegistry::default().iter()));
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn a_file_does_not_replace_a_predefined_theme() -> TestResult {
+    let dir = theme_dir("predefined")?;
+    let path = dir.join("warm.toml");
+    let mut registry = ThemeRegistry::default();
+    let mut loader = ThemeLoader::new(&dir);
+
+    write(&path, &YELLOW.replace("Yellow", "Warm"), at(1))?;
+    assert_eq!(
+        loader.load(&mut registry),
+        vec![LoadThemeError::NameTaken(path.clone(), "Warm".to_owned())]
+    );
+    assert_eq!(
+        registry.find("Warm").map(|named| named.theme),
+        Some(utils::WARM)
+    );
+
+    std::fs::remove_file(&path)?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.iter().eq(ThemeRegistry::default().iter()));
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn two_files_do_not_share_a_theme() -> TestResult {
+    let dir = theme_dir("same_name")?;
+    let first = dir.join("a.toml");
+    let second = dir.join("b.toml");
+    let mut registry = ThemeRegistry::default();
+    let mut loader = ThemeLoader::new(&dir);
+
+    write(&first, YELLOW, at(1))?;
+    write(&second, &YELLOW.replace("#e8d207", "Crimson"), at(1))?;
+    assert_eq!(
+        loader.load(&mut registry),
+        vec![LoadThemeError::NameTaken(
+            second.clone(),
+            "Yellow".to_owned()
+        )]
+    );
+    let yellow = registry.find("Yellow").ok_or("not added")?;
+    assert_eq!(yellow.theme[8], ThemeColor::Custom([0xe8, 0xd2, 0x07]));
+
+    std::fs::remove_file(&second)?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Yellow").is_some());
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn a_failed_file_is_read_again() -> TestResult {
+    let dir = theme_dir("retry")?;
+    let path = dir.join("yellow.toml");
+    let mut registry = ThemeRegistry::default();
+    let mut loader = ThemeLoader::new(&dir);
+
+    // half written, and completed within the same timer tick
+    let now = SystemTime::now();
+    write(&path, &YELLOW[..40], now)?;
+    assert_eq!(loader.load(&mut registry).len(), 1);
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Yellow").is_none());
+
+    write(&path, YELLOW, now)?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Yellow").is_some());
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn an_error_is_reported_once() -> TestResult {
+    let dir = theme_dir("report_once")?;
+    let path = dir.join("yellow.toml");
+    let mut registry = ThemeRegistry::default();
+    let mut loader = ThemeLoader::new(&dir);
+
+    write(&path, &YELLOW[..40], at(1))?;
+    assert_eq!(loader.load(&mut registry).len(), 1);
+    assert_eq!(loader.load(&mut registry), vec![]);
+
+    write(&path, &YELLOW[..50], at(2))?;
+    assert_eq!(loader.load(&mut registry).len(), 1);
+
+    write(&path, YELLOW, at(2))?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Yellow").is_none());
+
+    write(&path, YELLOW, at(3))?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Yellow").is_some());
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn a_theme_renamed_to_a_taken_name_is_removed() -> TestResult {
+    let dir = theme_dir("rename_taken")?;
+    let path = dir.join("yellow.toml");
+    let mut registry = ThemeRegistry::default();
+    let mut loader = ThemeLoader::new(&dir);
+
+    write(&path, YELLOW, at(1))?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Yellow").is_some());
+
+    write(&path, &YELLOW.replace("Yellow", "Warm"), at(2))?;
+    assert_eq!(
+        loader.load(&mut registry),
+        vec![LoadThemeError::NameTaken(path.clone(), "Warm".to_owned())]
+    );
+    assert!(registry.iter().eq(ThemeRegistry::default().iter()));
+
+    write(&path, YELLOW, at(3))?;
+    assert_eq!(loader.load(&mut registry), vec![]);
+    assert!(registry.find("Yellow").is_some());
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}